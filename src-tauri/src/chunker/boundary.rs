@@ -0,0 +1,71 @@
+// src/chunker/boundary.rs
+
+use regex::Regex;
+
+use super::{trim_span, window, Span};
+use crate::utils::estimate_tokens;
+
+/// 빈 줄로 구분된 문단 위치
+pub fn paragraphs(text: &str, span: Span) -> Vec<Span> {
+    let re = Regex::new(r"\n[ \t\r]*\n").unwrap();
+    split_by(text, span, &re)
+}
+
+/// 문장 위치 (마침표/물음표/느낌표 + 공백, 또는 빈 줄에서 나눔)
+pub fn sentences(text: &str, span: Span) -> Vec<Span> {
+    let re = Regex::new(r#"([.!?。！？]+["'”’)\]]*)\s+|\n[ \t\r]*\n"#).unwrap();
+    split_by(text, span, &re)
+}
+
+/// 구분자 앞까지를 한 단위로 자릅니다. (캡처 그룹 1이 있으면 그 부분은 앞 단위에 포함)
+fn split_by(text: &str, (start, end): Span, re: &Regex) -> Vec<Span> {
+    let mut units = Vec::new();
+    let mut unit_start = start;
+
+    for caps in re.captures_iter(&text[start..end]) {
+        let m = caps.get(0).unwrap();
+        let keep = caps.get(1).map_or(0, |g| g.len());
+        units.extend(trim_span(text, (unit_start, start + m.start() + keep)));
+        unit_start = start + m.end();
+    }
+    units.extend(trim_span(text, (unit_start, end)));
+
+    units
+}
+
+/// 연속된 단위(문단/문장)를 max_tokens까지 묶습니다.
+/// 다음 청크는 앞 청크의 마지막 단위들을 overlap_tokens 이내에서 다시 포함하고,
+/// 혼자서 max_tokens를 넘는 단위는 (앞에 모아둔 단위와 함께) 토큰 윈도우로 나눕니다.
+pub fn pack(text: &str, units: &[Span], max_tokens: usize, overlap_tokens: usize) -> Vec<Span> {
+    let tokens: Vec<usize> = units.iter().map(|&(s, e)| estimate_tokens(&text[s..e])).collect();
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    while i < units.len() {
+        let mut j = i;
+        let mut total = 0;
+        while j < units.len() && total + tokens[j] <= max_tokens {
+            total += tokens[j];
+            j += 1;
+        }
+
+        if j < units.len() && tokens[j] > max_tokens {
+            spans.extend(window::split(text, (units[i].0, units[j].1), max_tokens, overlap_tokens));
+            i = j + 1;
+            continue;
+        }
+
+        spans.push((units[i].0, units[j - 1].1));
+        if j >= units.len() { break; }
+
+        let mut k = j;
+        let mut back = 0;
+        while k > i + 1 && back + tokens[k - 1] <= overlap_tokens {
+            back += tokens[k - 1];
+            k -= 1;
+        }
+        i = k;
+    }
+
+    spans
+}
@@ -0,0 +1,122 @@
+// src/chunker/heading.rs
+
+use regex::Regex;
+
+use super::Span;
+
+/// 제목으로 볼 줄의 최대 길이 (글자 수)
+const MAX_HEADING_CHARS: usize = 80;
+
+/// 제목 줄을 기준으로 문서를 섹션으로 나눕니다.
+/// 첫 제목 앞의 내용은 제목 없는 섹션이 됩니다.
+pub fn sections(text: &str) -> Vec<(Option<String>, Span)> {
+    let patterns = HeadingPatterns::new();
+    let mut sections = Vec::new();
+    let mut current: (Option<String>, usize) = (None, 0);
+    let mut pos = 0;
+
+    for line in text.split_inclusive('\n') {
+        if let Some(title) = patterns.title(line) {
+            if pos > current.1 {
+                sections.push((current.0.take(), (current.1, pos)));
+            }
+            current = (Some(title), pos);
+        }
+        pos += line.len();
+    }
+    if text.len() > current.1 {
+        sections.push((current.0, (current.1, text.len())));
+    }
+
+    sections
+}
+
+struct HeadingPatterns {
+    markdown: Regex,
+    numbered: Regex,
+}
+
+impl HeadingPatterns {
+    fn new() -> Self {
+        Self {
+            markdown: Regex::new(r"^#{1,6}\s+(\S.*)$").unwrap(),
+            // 숫자만 있는 번호는 "1." 처럼 점이 있거나 "2.3" 처럼 절 번호 형태여야 함 ("2024 매출은", "3 개의" 제외)
+            numbered: Regex::new(r"^(?:제\s*\d+\s*[편장절]|(?i:chapter|section)\s+\d+|\d+(?:\.\d+)+\.?|\d+\.|[IVX]+\.)\s+\S").unwrap(),
+        }
+    }
+
+    /// 제목 줄이면 제목 텍스트를 반환합니다.
+    /// 인식하는 형태: "# 제목"(Markdown), "1. 제목" / "2.3 제목", "제1장 제목", "Chapter 1 ...", "IV. 제목"
+    fn title(&self, line: &str) -> Option<String> {
+        let line = line.trim();
+        if line.is_empty() || line.chars().count() > MAX_HEADING_CHARS { return None; }
+
+        if let Some(caps) = self.markdown.captures(line) {
+            return Some(caps[1].trim().to_string());
+        }
+
+        // 문장으로 끝나는 번호 목록("1. 회의는 3시에 시작했다", "2.5 배 늘었다.")은 제목이 아님
+        if self.numbered.is_match(line) && !ends_like_sentence(line) {
+            return Some(line.to_string());
+        }
+
+        None
+    }
+}
+
+/// 문장 끝으로 볼 끝말 (문장부호, 서술형 어미)
+/// "다" 한 글자로 보면 "2.3 바다" 같은 제목도 빠지므로 어미 단위로 봄
+const SENTENCE_ENDINGS: &[&str] = &[
+    ".", ",", ";", ":",
+    "니다", "이다", "였다", "었다", "았다", "했다", "한다", "된다", "있다", "없다", "요",
+];
+
+fn ends_like_sentence(line: &str) -> bool {
+    SENTENCE_ENDINGS.iter().any(|e| line.ends_with(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sections, HeadingPatterns};
+
+    #[test]
+    fn recognizes_heading_forms() {
+        let p = HeadingPatterns::new();
+        assert_eq!(p.title("# 개요\n").as_deref(), Some("개요"));
+        assert_eq!(p.title("### Install steps").as_deref(), Some("Install steps"));
+        for line in ["1. 서론", "2.3 실험 방법", "2.3. 결과", "제1장 총칙", "제 2 절 정의", "Chapter 3 Results", "IV. 결론", "2.3 바다"] {
+            assert_eq!(p.title(line).as_deref(), Some(line), "{}", line);
+        }
+    }
+
+    #[test]
+    fn ignores_plain_lines_starting_with_numbers() {
+        let p = HeadingPatterns::new();
+        for line in [
+            "2024 매출은 전년 대비 증가",
+            "3 개의 지점을 새로 열었음",
+            "1. 회의는 3시에 시작했다",
+            "2.5 배 늘어난 수치입니다.",
+            "4. 예산은 내년에 확정된다",
+            "10 minutes later",
+            "",
+        ] {
+            assert_eq!(p.title(line), None, "{}", line);
+        }
+    }
+
+    #[test]
+    fn splits_text_into_titled_sections() {
+        let text = "머리말\n# 개요\n본문 1\n2024 매출은 늘었음\n2. 방법\n본문 2\n";
+        let found: Vec<(Option<String>, &str)> = sections(text)
+            .into_iter()
+            .map(|(title, (start, end))| (title, &text[start..end]))
+            .collect();
+
+        assert_eq!(found, vec![
+            (None, "머리말\n"),
+            (Some("개요".to_string()), "# 개요\n본문 1\n2024 매출은 늘었음\n"),
+            (Some("2. 방법".to_string()), "2. 방법\n본문 2\n"),
+        ]);
+    }
+}
@@ -0,0 +1,174 @@
+// src/chunker/mod.rs
+
+pub mod window;
+pub mod boundary;
+pub mod heading;
+
+use serde::{Deserialize, Serialize};
+
+/// 텍스트 안의 바이트 범위 [start, end)
+pub type Span = (usize, usize);
+
+/// 페이지를 하나의 문서 텍스트로 이을 때 사이에 넣는 구분자
+pub const PAGE_SEPARATOR: &str = "\n\n";
+
+/// 청크 분할 방식
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkStrategy {
+    /// 1페이지 = 1청크 (기존 방식)
+    #[default]
+    Page,
+    /// 고정 토큰 윈도우 + 겹침
+    Window,
+    /// 빈 줄로 구분된 문단을 max_tokens까지 묶음
+    Paragraph,
+    /// 문장을 max_tokens까지 묶음
+    Sentence,
+    /// 제목(Heading) 기준 섹션 단위, 긴 섹션은 문단으로 다시 나눔
+    Heading,
+}
+
+/// 청크 분할 옵션 (토큰 수는 utils::estimate_tokens 기준 추정치)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ChunkerOptions {
+    pub strategy: ChunkStrategy,
+    /// 청크 하나의 최대 토큰 수
+    pub max_tokens: usize,
+    /// 이웃한 청크끼리 겹칠 토큰 수 (문맥 끊김 방지)
+    pub overlap_tokens: usize,
+}
+
+impl Default for ChunkerOptions {
+    fn default() -> Self {
+        Self { strategy: ChunkStrategy::default(), max_tokens: 400, overlap_tokens: 50 }
+    }
+}
+
+/// 잘라낸 청크 + 원문에서의 위치 (인용 시 페이지를 찾기 위함)
+#[derive(Debug, Clone)]
+pub struct TextChunk {
+    pub content: String,
+    /// 1부터 시작하는 페이지 범위 (청크가 여러 페이지에 걸칠 수 있음)
+    pub page_start: usize,
+    pub page_end: usize,
+    /// 문서 전체 텍스트(페이지를 PAGE_SEPARATOR로 이은 것) 기준 글자(char) 오프셋 [start, end)
+    pub char_start: usize,
+    pub char_end: usize,
+    /// Heading 전략일 때 청크가 속한 섹션 제목
+    pub heading: Option<String>,
+}
+
+/// 페이지 목록을 선택한 전략으로 청크로 나눕니다.
+pub fn chunk_pages(pages: &[String], opts: &ChunkerOptions) -> Vec<TextChunk> {
+    let doc = PagedText::new(pages);
+    let text = doc.text.as_str();
+    let whole = (0, text.len());
+    let max_tokens = opts.max_tokens.max(1);
+    // 겹침이 윈도우의 절반을 넘으면 진행이 너무 느려지므로 제한
+    let overlap = opts.overlap_tokens.min(max_tokens / 2);
+
+    let pieces: Vec<(Span, Option<String>)> = match opts.strategy {
+        ChunkStrategy::Page => doc.page_spans().into_iter().map(|s| (s, None)).collect(),
+        ChunkStrategy::Window => without_heading(window::split(text, whole, max_tokens, overlap)),
+        ChunkStrategy::Paragraph => {
+            without_heading(boundary::pack(text, &boundary::paragraphs(text, whole), max_tokens, overlap))
+        }
+        ChunkStrategy::Sentence => {
+            without_heading(boundary::pack(text, &boundary::sentences(text, whole), max_tokens, overlap))
+        }
+        ChunkStrategy::Heading => heading::sections(text)
+            .into_iter()
+            .flat_map(|(title, section)| {
+                boundary::pack(text, &boundary::paragraphs(text, section), max_tokens, overlap)
+                    .into_iter()
+                    .map(move |s| (s, title.clone()))
+            })
+            .collect(),
+    };
+
+    doc.to_chunks(pieces)
+}
+
+fn without_heading(spans: Vec<Span>) -> Vec<(Span, Option<String>)> {
+    spans.into_iter().map(|s| (s, None)).collect()
+}
+
+/// 앞뒤 공백을 제외한 범위 (내용이 없으면 None)
+pub fn trim_span(text: &str, (start, end): Span) -> Option<Span> {
+    let slice = &text[start..end];
+    let lead = slice.len() - slice.trim_start().len();
+    let trail = slice.len() - slice.trim_end().len();
+    if lead == slice.len() { None } else { Some((start + lead, end - trail)) }
+}
+
+/// 페이지를 이어붙인 문서 텍스트 + 각 페이지의 시작 위치
+struct PagedText {
+    text: String,
+    page_starts: Vec<usize>,
+}
+
+impl PagedText {
+    fn new(pages: &[String]) -> Self {
+        let mut text = String::new();
+        let mut page_starts = Vec::with_capacity(pages.len());
+        for (i, page) in pages.iter().enumerate() {
+            if i > 0 { text.push_str(PAGE_SEPARATOR); }
+            page_starts.push(text.len());
+            text.push_str(page);
+        }
+        Self { text, page_starts }
+    }
+
+    fn page_spans(&self) -> Vec<Span> {
+        self.page_starts.iter().enumerate().map(|(i, &start)| {
+            let end = self.page_starts.get(i + 1).map_or(self.text.len(), |&next| next - PAGE_SEPARATOR.len());
+            (start, end)
+        }).collect()
+    }
+
+    /// 바이트 위치가 속한 페이지 번호 (1부터)
+    fn page_at(&self, byte: usize) -> usize {
+        self.page_starts.partition_point(|&s| s <= byte).max(1)
+    }
+
+    fn to_chunks(&self, pieces: Vec<(Span, Option<String>)>) -> Vec<TextChunk> {
+        let mut chars = CharCounter::new(&self.text);
+
+        pieces.into_iter().filter_map(|(span, heading)| {
+            let (start, end) = trim_span(&self.text, span)?;
+            Some(TextChunk {
+                content: self.text[start..end].to_string(),
+                page_start: self.page_at(start),
+                page_end: self.page_at(end - 1),
+                char_start: chars.at(start),
+                char_end: chars.at(end),
+                heading,
+            })
+        }).collect()
+    }
+}
+
+/// 바이트 오프셋 -> 글자 오프셋 변환 (청크는 대부분 앞에서부터 순서대로 오므로 마지막 위치부터 이어서 셈)
+struct CharCounter<'a> {
+    text: &'a str,
+    byte: usize,
+    chars: usize,
+}
+
+impl<'a> CharCounter<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, byte: 0, chars: 0 }
+    }
+
+    fn at(&mut self, byte: usize) -> usize {
+        if byte < self.byte {
+            self.byte = 0;
+            self.chars = 0;
+        }
+        self.chars += self.text[self.byte..byte].chars().count();
+        self.byte = byte;
+        self.chars
+    }
+}
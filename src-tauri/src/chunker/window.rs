@@ -0,0 +1,79 @@
+// src/chunker/window.rs
+
+use super::Span;
+use crate::utils::estimate_tokens;
+
+/// 고정 토큰 윈도우로 자릅니다.
+/// 단어(공백 기준)를 max_tokens까지 담고, 다음 청크는 끝에서 overlap_tokens만큼 되돌아가서 시작합니다.
+pub fn split(text: &str, span: Span, max_tokens: usize, overlap_tokens: usize) -> Vec<Span> {
+    let words = words(text, span, max_tokens);
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    while i < words.len() {
+        let mut j = i;
+        let mut tokens = 0;
+        while j < words.len() && (j == i || tokens + words[j].1 <= max_tokens) {
+            tokens += words[j].1;
+            j += 1;
+        }
+        spans.push((words[i].0.0, words[j - 1].0.1));
+        if j >= words.len() { break; }
+
+        // 겹침: 끝에서부터 overlap_tokens 이내의 단어를 다음 청크에 다시 포함 (최소 한 단어는 전진)
+        let mut k = j;
+        let mut back = 0;
+        while k > i + 1 && back + words[k - 1].1 <= overlap_tokens {
+            back += words[k - 1].1;
+            k -= 1;
+        }
+        i = k;
+    }
+
+    spans
+}
+
+/// 범위 안의 단어 위치와 추정 토큰 수 (앞쪽 공백 포함)
+/// 띄어쓰기가 없는 긴 문자열(한자, 깨진 PDF 텍스트 등)은 max_tokens 크기로 다시 나눕니다.
+fn words(text: &str, span: Span, max_tokens: usize) -> Vec<(Span, usize)> {
+    let mut out = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut gap_start = span.0;
+
+    let slice = &text[span.0..span.1];
+    for (offset, c) in slice.char_indices().chain(std::iter::once((slice.len(), ' '))) {
+        let pos = span.0 + offset;
+        if c.is_whitespace() {
+            if let Some(s) = start.take() {
+                push_word(text, (s, pos), estimate_tokens(&text[gap_start..s]), max_tokens, &mut out);
+                gap_start = pos;
+            }
+        } else if start.is_none() {
+            start = Some(pos);
+        }
+    }
+
+    out
+}
+
+fn push_word(text: &str, (start, end): Span, gap_tokens: usize, max_tokens: usize, out: &mut Vec<(Span, usize)>) {
+    let tokens = gap_tokens + estimate_tokens(&text[start..end]);
+    if tokens <= max_tokens {
+        out.push(((start, end), tokens));
+        return;
+    }
+
+    let mut piece_start = start;
+    let mut piece_tokens = 0;
+    for (offset, c) in text[start..end].char_indices() {
+        let pos = start + offset;
+        let t = estimate_tokens(c.encode_utf8(&mut [0; 4]));
+        if piece_tokens + t > max_tokens && pos > piece_start {
+            out.push(((piece_start, pos), piece_tokens));
+            piece_start = pos;
+            piece_tokens = 0;
+        }
+        piece_tokens += t;
+    }
+    out.push(((piece_start, end), piece_tokens));
+}
@@ -0,0 +1,196 @@
+use tauri::ipc::Channel;
+use tauri::State;
+use serde::Serialize;
+
+use surrealdb::sql::Thing;
+
+use crate::commands::conversation::{conversation_exists, load_history, save_message};
+use crate::llm::budget::{BudgetReport, PromptBudget, Tokenizer};
+use crate::llm::chat::{stream_chat, ChatMessage};
+use crate::llm::rewrite::rewrite_query;
+use crate::models::{Citation, RetrievedChunk};
+use crate::retrieval::filter::RetrievalFilter;
+use crate::retrieval::rerank::RerankOptions;
+use crate::retrieval::{retrieve, RetrievalOptions, SearchMode};
+use crate::AppState;
+
+/// 답변 생성용으로 비워둘 토큰 수 (나머지 ctx-size에 프롬프트를 채움)
+const ANSWER_RESERVE_TOKENS: usize = 512;
+/// 프롬프트에 넣을 이전 대화의 최대 토큰 수 (추정치)
+const HISTORY_TOKEN_BUDGET: usize = 1024;
+
+const SYSTEM_PROMPT: &str = r#"
+당신은 사용자가 제공한 문서를 기반으로 답변하는 AI 비서입니다.
+[참고 문서]의 각 항목은 [번호]로 시작합니다. 답변에 사용한 문서의 번호를 문장 끝에 [1], [2]처럼 표기하세요.
+참고 문서에 내용이 없다면, 일반적인 지식으로 답변하되 문서에 없다고 언급해주세요.
+"#;
+
+/// 프론트엔드로 스트리밍되는 이벤트
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "camelCase")]
+pub enum ChatStreamEvent {
+    /// 검색 완료 (컨텍스트로 사용할 Chunk 수)
+    Retrieved { count: usize },
+    /// 생성된 토큰 조각
+    Token { text: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChatAnswer {
+    pub answer: String,
+    pub citations: Vec<Citation>,
+    /// 대화에 저장된 경우 assistant 메시지 ID
+    pub message_id: Option<String>,
+    /// 프롬프트 토큰 사용량과 ctx-size 때문에 잘리거나 빠진 참고 문서
+    pub budget: BudgetReport,
+}
+
+// --- 문서 기반 RAG 채팅 (토큰 스트리밍 + 출처 반환) ---
+// 인자는 프론트엔드 invoke의 이름 있는 인자와 1:1 대응
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn chat_with_docs(
+    question: String,
+    top_k: Option<usize>,
+    mode: Option<SearchMode>,
+    rerank: Option<RerankOptions>,
+    filter: Option<RetrievalFilter>,
+    conversation_id: Option<String>,
+    on_event: Channel<ChatStreamEvent>,
+    state: State<'_, AppState>,
+) -> Result<ChatAnswer, String> {
+    let db = &state.db;
+    let question = question.trim().to_string();
+    if question.is_empty() { return Err("질문이 비어있습니다.".to_string()); }
+
+    println!("\n💬 chat_with_docs: \"{}\"", question);
+
+    // 0. 대화 기록 불러오기 (없는 대화에 메시지만 쌓이지 않도록 먼저 확인)
+    let conversation = conversation_id.as_deref().map(|id| Thing::from(("conversation", id)));
+    if let Some(c) = &conversation {
+        if !conversation_exists(db, c).await.map_err(|e| e.to_string())? {
+            return Err(format!("대화를 찾을 수 없습니다: {}", c.id.to_raw()));
+        }
+    }
+    let history = match &conversation {
+        Some(c) => load_history(db, c, HISTORY_TOKEN_BUDGET).await.map_err(|e| e.to_string())?,
+        None => vec![],
+    };
+
+    // 후속 질문("그 회사 매출은?")은 대화 맥락을 반영해 독립적인 검색 질의로 재작성
+    let rewritten_query = if history.is_empty() { None } else {
+        match rewrite_query(&state.chat_endpoint(), &history, &question).await {
+            Ok(q) => {
+                println!("    ✏️ Rewritten query: \"{}\"", q);
+                Some(q)
+            }
+            Err(e) => {
+                println!("    ⚠️ Query rewrite failed: {}", e);
+                None
+            }
+        }
+    };
+    let search_query = rewritten_query.clone().unwrap_or_else(|| question.clone());
+
+    // 질문 저장 (재작성된 질의도 함께 기록)
+    if let Some(c) = &conversation {
+        save_message(db, c, "user", &question, vec![], rewritten_query).await.map_err(|e| e.to_string())?;
+    }
+
+    // 1. 검색 (실패해도 대화는 가능하게 빈 컨텍스트로 진행)
+    let defaults = state.settings().retrieval;
+    let opts = RetrievalOptions {
+        top_k: top_k.unwrap_or(defaults.top_k),
+        mode: mode.unwrap_or(SearchMode::Hybrid),
+        rerank: rerank.unwrap_or(defaults.rerank),
+        filter: filter.unwrap_or_default(),
+        ..Default::default()
+    };
+    let passages = retrieve(&state, &search_query, &opts).await.unwrap_or_else(|e| {
+        println!("    ⚠️ Retrieval failed: {}", e);
+        vec![]
+    });
+    let _ = on_event.send(ChatStreamEvent::Retrieved { count: passages.len() });
+
+    // 2. 프롬프트 구성 (시스템 프롬프트/대화 기록/질문을 먼저 넣고, 남은 ctx-size만큼 참고 문서를 채움)
+    let endpoint = state.chat_endpoint();
+    let tokenizer = Tokenizer::for_endpoint(&endpoint);
+    let mut budget = PromptBudget::new(&tokenizer, endpoint.ctx_size, ANSWER_RESERVE_TOKENS);
+    budget.add_message(SYSTEM_PROMPT.trim()).await;
+    for m in &history {
+        budget.add_message(&m.content).await;
+    }
+    budget.add_message(&format!("[참고 문서]\n\n[질문]\n{}", question)).await;
+    let passages = fit_passages(&mut budget, passages).await;
+    let budget = budget.finish();
+    budget.log();
+
+    let mut messages = vec![ChatMessage::system(SYSTEM_PROMPT.trim())];
+    messages.extend(history);
+    messages.push(ChatMessage::user(format!(
+        "[참고 문서]\n{}\n\n[질문]\n{}",
+        build_context(&passages),
+        question
+    )));
+
+    // 3. 생성 (토큰 단위 스트리밍)
+    let answer = stream_chat(&endpoint, &messages, 0.3, |token| {
+        let _ = on_event.send(ChatStreamEvent::Token { text: token.to_string() });
+    }).await.map_err(|e| e.to_string())?;
+
+    // 4. 답변 저장 (message -> cited -> chunk)
+    let citations = build_citations(&passages, &answer);
+    let message_id = match &conversation {
+        Some(c) => {
+            let id = save_message(db, c, "assistant", &answer, citations.clone(), None).await.map_err(|e| e.to_string())?;
+            Some(id.id.to_raw())
+        }
+        None => None,
+    };
+
+    Ok(ChatAnswer { answer, citations, message_id, budget })
+}
+
+/// 순위대로 참고 문서를 예산에 맞춰 자르고, 자리가 없는 문서는 제외합니다.
+/// (제외된 문서는 인용 번호에서도 빠짐)
+async fn fit_passages(budget: &mut PromptBudget<'_>, passages: Vec<RetrievedChunk>) -> Vec<RetrievedChunk> {
+    let mut kept = Vec::new();
+    for mut p in passages {
+        let header = passage_header(kept.len() + 1, &p);
+        if let Some(content) = budget.fit_with_header(&header, p.content.trim()).await {
+            p.content = content;
+            kept.push(p);
+        }
+    }
+    kept
+}
+
+fn passage_header(index: usize, p: &RetrievedChunk) -> String {
+    format!("[{}] ({} p.{})", index, p.filename, p.page_number)
+}
+
+/// 검색된 Chunk를 번호가 붙은 컨텍스트 문자열로 만듭니다.
+pub(crate) fn build_context(passages: &[RetrievedChunk]) -> String {
+    if passages.is_empty() {
+        return "(관련된 문서를 찾지 못했습니다.)".to_string();
+    }
+
+    passages.iter().enumerate()
+        .map(|(i, p)| format!("{}\n{}", passage_header(i + 1, p), p.content.trim()))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+pub(crate) fn build_citations(passages: &[RetrievedChunk], answer: &str) -> Vec<Citation> {
+    passages.iter().enumerate()
+        .map(|(i, p)| Citation {
+            index: i + 1,
+            chunk_id: p.id.clone(),
+            document_id: p.document_id.clone(),
+            filename: p.filename.clone(),
+            page_number: p.page_number,
+            score: p.score,
+            cited: answer.contains(&format!("[{}]", i + 1)),
+        })
+        .collect()
+}
@@ -0,0 +1,184 @@
+use tauri::State;
+use chrono::Utc;
+use surrealdb::engine::local::Db;
+use surrealdb::sql::Thing;
+use surrealdb::Surreal;
+use uuid::Uuid;
+
+use crate::llm::chat::ChatMessage;
+use crate::models::{Citation, ConversationNode, ConversationSummary, MessageNode, MessageRecord};
+use crate::utils::estimate_tokens;
+use crate::AppState;
+
+pub const DEFAULT_CONVERSATION_TITLE: &str = "새 대화";
+
+// --- 새 대화 생성 ---
+#[tauri::command]
+pub async fn create_conversation(
+    title: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ConversationSummary, String> {
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now();
+    let title = title.map(|t| t.trim().to_string()).filter(|t| !t.is_empty())
+        .unwrap_or_else(|| DEFAULT_CONVERSATION_TITLE.to_string());
+
+    let _: ConversationNode = state.db.create(("conversation", &id))
+        .content(ConversationNode { id: None, title: title.clone(), created_at: now, updated_at: now })
+        .await.map_err(|e| e.to_string())?.ok_or("Conversation create failed")?;
+
+    Ok(ConversationSummary { id, title, created_at: now, updated_at: now, message_count: 0 })
+}
+
+// --- 대화 목록 (최근 활동 순) ---
+#[tauri::command]
+pub async fn list_conversations(state: State<'_, AppState>) -> Result<Vec<ConversationSummary>, String> {
+    let sql = "
+        SELECT
+            meta::id(id) AS id, title, created_at, updated_at,
+            count((SELECT id FROM message WHERE conversation = $parent.id)) AS message_count
+        FROM conversation
+        ORDER BY updated_at DESC
+    ";
+
+    state.db.query(sql)
+        .await.map_err(|e| e.to_string())?
+        .take(0).map_err(|e| e.to_string())
+}
+
+// --- 대화 제목 변경 ---
+#[tauri::command]
+pub async fn rename_conversation(
+    id: String,
+    title: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let title = title.trim().to_string();
+    if title.is_empty() { return Err("제목이 비어있습니다.".to_string()); }
+
+    state.db.query("UPDATE $c SET title = $title, updated_at = time::now()")
+        .bind(("c", Thing::from(("conversation", id.as_str()))))
+        .bind(("title", title))
+        .await.map_err(|e| e.to_string())?
+        .check().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// --- 대화 삭제 (메시지 + cited Edge 포함) ---
+#[tauri::command]
+pub async fn delete_conversation(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let sql = "
+        DELETE cited WHERE in.conversation = $c;
+        DELETE message WHERE conversation = $c;
+        DELETE $c;
+    ";
+
+    state.db.query(sql)
+        .bind(("c", Thing::from(("conversation", id.as_str()))))
+        .await.map_err(|e| e.to_string())?
+        .check().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// --- 대화 이어하기: 저장된 메시지 전체 조회 (오래된 순) ---
+#[tauri::command]
+pub async fn get_conversation_messages(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<MessageRecord>, String> {
+    let sql = "
+        SELECT meta::id(id) AS id, role, content, citations, rewritten_query, created_at
+        FROM message
+        WHERE conversation = $c
+        ORDER BY created_at ASC
+    ";
+
+    state.db.query(sql)
+        .bind(("c", Thing::from(("conversation", id.as_str()))))
+        .await.map_err(|e| e.to_string())?
+        .take(0).map_err(|e| e.to_string())
+}
+
+/// 대화가 존재하는지 (삭제됐거나 잘못된 ID면 false)
+pub(crate) async fn conversation_exists(db: &Surreal<Db>, conversation: &Thing) -> surrealdb::Result<bool> {
+    let ids: Vec<Thing> = db
+        .query("SELECT VALUE id FROM $c")
+        .bind(("c", conversation.clone()))
+        .await?
+        .take(0)?;
+    Ok(!ids.is_empty())
+}
+
+/// 최근 메시지부터 `token_budget` 안에 들어가는 만큼만 골라 시간순으로 반환합니다.
+pub(crate) async fn load_history(
+    db: &Surreal<Db>,
+    conversation: &Thing,
+    token_budget: usize,
+) -> surrealdb::Result<Vec<ChatMessage>> {
+    let recent: Vec<ChatMessage> = db
+        .query("SELECT role, content, created_at FROM message WHERE conversation = $c ORDER BY created_at DESC LIMIT 50")
+        .bind(("c", conversation.clone()))
+        .await?
+        .take(0)?;
+
+    let mut used = 0;
+    let mut history: Vec<ChatMessage> = recent.into_iter()
+        .take_while(|m| {
+            used += estimate_tokens(&m.content);
+            used <= token_budget
+        })
+        .collect();
+    history.reverse();
+
+    Ok(history)
+}
+
+/// 메시지를 저장하고, assistant 메시지라면 인용한 Chunk와 `message -> cited -> chunk`로 연결합니다.
+pub(crate) async fn save_message(
+    db: &Surreal<Db>,
+    conversation: &Thing,
+    role: &str,
+    content: &str,
+    citations: Vec<Citation>,
+    rewritten_query: Option<String>,
+) -> surrealdb::Result<Thing> {
+    let key = Uuid::new_v4().to_string();
+    let message_id = Thing::from(("message", key.as_str()));
+
+    let _: Option<MessageNode> = db.create(("message", &key))
+        .content(MessageNode {
+            id: None,
+            conversation: conversation.clone(),
+            role: role.to_string(),
+            content: content.to_string(),
+            citations: citations.clone(),
+            rewritten_query,
+            created_at: Utc::now(),
+        })
+        .await?;
+
+    for c in citations.iter().filter(|c| c.cited) {
+        let Ok(chunk) = surrealdb::sql::thing(&c.chunk_id) else { continue };
+        db.query("RELATE $m->cited->$c SET citation_index = $idx, score = $score")
+            .bind(("m", message_id.clone()))
+            .bind(("c", chunk))
+            .bind(("idx", c.index))
+            .bind(("score", c.score))
+            .await?;
+    }
+
+    // 첫 질문이면 대화 제목을 질문으로 바꿔줌
+    db.query("UPDATE $c SET updated_at = time::now(); UPDATE $c SET title = $title WHERE title = $default AND $role = 'user'")
+        .bind(("c", conversation.clone()))
+        .bind(("title", content.chars().take(30).collect::<String>()))
+        .bind(("default", DEFAULT_CONVERSATION_TITLE))
+        .bind(("role", role.to_string()))
+        .await?;
+
+    Ok(message_id)
+}
@@ -0,0 +1,69 @@
+use tauri::AppHandle;
+use serde::Deserialize;
+use surrealdb::sql::Thing;
+
+use crate::jobs::{self, Job, JobContext, JobRequest};
+use crate::llm::embedder::{embed_texts, EMBED_BATCH_SIZE};
+use crate::AppState;
+
+/// 임베딩이 비어있는 레코드 (id + 임베딩할 텍스트)
+#[derive(Debug, Deserialize)]
+struct PendingEmbedding {
+    id: Thing,
+    text: String,
+}
+
+// --- 기존 Chunk / Entity 중 벡터가 없는 레코드에 임베딩 보충 (백그라운드 작업) ---
+#[tauri::command]
+pub async fn backfill_embeddings(app: AppHandle) -> Result<Job, String> {
+    jobs::submit(&app, JobRequest::Embed).await
+}
+
+/// 임베딩 보충 작업 본문 (배치 사이마다 일시정지/취소 요청 확인)
+pub async fn backfill_all(state: &AppState, ctx: &JobContext) -> Result<String, String> {
+    println!("\n🧬 [Backfill] Embedding chunks/entities without vectors...");
+
+    let chunk_count = backfill_table(state, ctx, "chunk", "SELECT id, content AS text FROM chunk WHERE embedding = NONE OR embedding = [] LIMIT $limit").await?;
+    let entity_count = backfill_table(state, ctx, "entity", "SELECT id, name AS text FROM entity WHERE embedding = NONE OR embedding = [] LIMIT $limit").await?;
+
+    Ok(format!("✅ 임베딩 보충 완료 (Chunk {}개, Entity {}개)", chunk_count, entity_count))
+}
+
+/// `sql`로 조회되는 레코드가 없어질 때까지 배치 단위로 임베딩을 생성해 저장합니다.
+async fn backfill_table(state: &AppState, ctx: &JobContext, table: &str, sql: &str) -> Result<usize, String> {
+    let db = &state.db;
+    let mut total = 0;
+
+    loop {
+        ctx.checkpoint()?;
+        let pending: Vec<PendingEmbedding> = db.query(sql)
+            .bind(("limit", EMBED_BATCH_SIZE * 4))
+            .await.map_err(|e| e.to_string())?
+            .take(0).map_err(|e| e.to_string())?;
+
+        if pending.is_empty() { break; }
+
+        let texts: Vec<String> = pending.iter().map(|p| p.text.clone()).collect();
+        // 임베딩 서버 오류 시 같은 레코드를 무한 반복하지 않도록 바로 중단
+        let vectors = embed_texts(&state.embed_client(), &texts).await.map_err(|e| e.to_string())?;
+
+        if let Some(dim) = vectors.first().map(|v| v.len()) {
+            crate::database::ensure_vector_index(db, table, dim).await.map_err(|e| e.to_string())?;
+        }
+
+        for (row, vector) in pending.into_iter().zip(vectors) {
+            if vector.is_empty() {
+                return Err(format!("Empty embedding returned for {}", row.id));
+            }
+            db.query("UPDATE $id SET embedding = $embedding")
+                .bind(("id", row.id))
+                .bind(("embedding", vector))
+                .await.map_err(|e| e.to_string())?;
+            total += 1;
+        }
+        println!("    ... {} embedded", total);
+        ctx.progress(total, 0, format!("{}: {} embedded", table, total)).await;
+    }
+
+    Ok(total)
+}
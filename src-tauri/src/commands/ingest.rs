@@ -1,259 +1,332 @@
-use tauri::State;
-use std::fs;
-use uuid::Uuid;
+use tauri::{AppHandle, State};
+use std::path::Path;
 use chrono::Utc;
 use surrealdb::sql::Thing;
-use std::collections::{HashMap, HashSet};
-use serde_json::json;
+use std::collections::HashMap;
+use serde_json::{json, Value as JsonValue};
 use std::time::Instant;
 
-use crate::models::{EventNode, DocumentNode, ChunkNode, EntityNode, DocumentWithChunks, CoreAnalysisResult};
-use crate::utils::extract_pages_from_pdf;
-use crate::llm::extractor::analyze_content;
+use crate::models::{EventNode, ChunkNode, DocumentWithChunks};
+use crate::chunker::ChunkerOptions;
+use crate::ingest::{ingest_file, IngestReport};
+use crate::ingest::scan::{scan_directory, ScanOptions};
+use crate::jobs::{self, Job, JobContext, JobRequest};
+use crate::llm::extractor::{analyze_content, key_entities_from};
+use crate::keywords::{extract_keywords, tfidf::Corpus, KeywordMode};
+use crate::resolution::merge::resolve_alias;
+use crate::resolution::normalize::normalize_entity_name;
 use crate::AppState;
 
-// --- 1단계: PDF 파일 Ingest 및 구조 분석 (LLM) ---
+// --- 1단계: 문서 파일 Ingest 및 구조 분석 (LLM) ---
+// 지원 형식: PDF, Markdown, TXT, HTML, DOCX, EPUB (loaders 모듈)
+// 내용이 같은 파일은 건너뛰고, 내용이 바뀐 파일은 Chunk를 교체합니다.
+// 하위 폴더는 설정의 ingest(include/exclude 패턴, 최대 깊이 등)에 따라 탐색합니다.
+// 백그라운드 작업(job)으로 실행되며, 진행 상황은 job-update 이벤트 / get_job으로 확인합니다.
 #[tauri::command]
 pub async fn ingest_documents(
     path: String,
-    state: State<'_, AppState>,
-) -> Result<String, String> {
+    chunking: Option<ChunkerOptions>,
+    scan: Option<ScanOptions>,
+    app: AppHandle,
+) -> Result<Job, String> {
+    if !Path::new(&path).is_dir() {
+        return Err(format!("Not a directory: {}", path));
+    }
+    jobs::submit(&app, JobRequest::Ingest { path, chunking, scan }).await
+}
+
+/// Ingest 작업 본문. 파일 사이마다 일시정지/취소 요청을 확인합니다.
+pub async fn run_ingest(
+    state: &AppState,
+    ctx: &JobContext,
+    path: &str,
+    chunking: Option<ChunkerOptions>,
+    scan: Option<ScanOptions>,
+) -> Result<IngestReport, String> {
     let db = &state.db;
-    let gen_url = "[http://127.0.0.1:8081/v1](http://127.0.0.1:8081/v1)"; // 로컬 LLM 서버 주소
+    // 청크 분할 방식, 파일 선택 규칙 (지정하지 않으면 설정값)
+    let chunking = chunking.unwrap_or_else(|| state.settings().chunking);
+    let scan = scan.unwrap_or_else(|| state.settings().ingest);
 
-    println!("\n📂 [Step 1] Ingest Process Started (1 Page = 1 Chunk)");
-    println!("    Target Directory: {}", path);
+    println!("\n📂 [Step 1] Ingest Process Started (Chunking: {:?}, max {} tokens)", chunking.strategy, chunking.max_tokens);
+    println!("    Target Directory: {} (recursive: {}, max depth {})", path, scan.recursive, scan.max_depth);
 
     // 1. 파일 목록 수집
-    let entries = fs::read_dir(&path).map_err(|e| e.to_string())?;
-    let mut pdf_files = Vec::new();
-    for entry in entries {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("pdf") {
-            pdf_files.push(path);
-        }
+    let root = Path::new(path);
+    let scanned = scan_directory(root, &scan).map_err(|e| e.to_string())?;
+    let mut report = IngestReport { skipped: scanned.skipped, ..Default::default() };
+    let files = scanned.files;
+
+    let total_files = files.len();
+    if total_files == 0 { return Err("No supported documents found.".to_string()); }
+
+    // 2. 세션 생성 (작업 기록용 Event, ID는 작업 ID와 같아서 재개해도 같은 Event에 이어서 연결)
+    let existing: Option<EventNode> = db.select(("event", ctx.id.as_str())).await.map_err(|e| e.to_string())?;
+    if existing.is_none() {
+        let _: EventNode = db.create(("event", ctx.id.as_str()))
+            .content(EventNode {
+                id: None, summary: format!("Document Ingest: {}", path), created_at: Utc::now(),
+            }).await.map_err(|e| e.to_string())?.ok_or("Event create failed")?;
     }
-    
-    let total_files = pdf_files.len();
-    if total_files == 0 { return Err("No PDF files found.".to_string()); }
-
-    // 2. 세션 생성 (작업 기록용 Event)
-    let session_id = Uuid::new_v4().to_string();
-    let _: EventNode = db.create(("event", &session_id))
-        .content(EventNode {
-            id: None, summary: format!("PDF Ingest: {}", path), created_at: Utc::now(),
-        }).await.map_err(|e| e.to_string())?.ok_or("Event create failed")?;
-
-    let mut success_count = 0;
+    let session = Thing::from(("event", ctx.id.as_str()));
 
     // 3. 파일 처리 루프
-    for (idx, file_path) in pdf_files.iter().enumerate() {
-        let current_num = idx + 1;
-        let original_filename = file_path.file_name().unwrap().to_string_lossy().to_string();
-        
+    for (idx, file) in files.iter().enumerate() {
+        ctx.checkpoint()?;
+        ctx.progress(idx, total_files, &file.relative_path).await;
         println!("\n---------------------------------------------------");
-        println!("▶️  [{}/{}] Processing: {}", current_num, total_files, original_filename);
-        
-        // A. 페이지별 텍스트 추출
-        print!("    📖 Extracting pages... ");
-        let pages = match extract_pages_from_pdf(file_path) {
-            Ok(p) => {
-                println!("Done ({} pages)", p.len());
-                p
-            },
-            Err(e) => {
-                println!("❌ Failed: {}", e);
-                continue;
-            }
-        };
+        println!("▶️  [{}/{}] Processing: {}", idx + 1, total_files, file.relative_path);
 
-        if pages.is_empty() { 
-            println!("    ⚠️ Skipped (Empty PDF)");
-            continue; 
-        }
+        let result = ingest_file(state, &file.path, Some(root), Some(&session), &chunking).await;
+        report.record(&file.path, result);
+    }
+    ctx.progress(total_files, total_files, report.summary()).await;
 
-        // B. Document(부모) 요약 생성 (앞 2페이지만 사용)
-        let summary_context = pages.iter().take(2).cloned().collect::<Vec<String>>().join("\n");
-        
-        println!("    🤖 Summarizing Document (Parent)...");
-        let parent_analysis = analyze_content(gen_url, &summary_context).await.unwrap_or_else(|_| {
-             CoreAnalysisResult {
-                topic: original_filename.clone(),
-                summary: "분석 실패".to_string(),
-                key_entities: vec![],
-                detailed_data: json!({}),
-            }
-        });
-
-        // Document 저장
-        let doc_id = Uuid::new_v4().to_string();
-        let mut doc_meta = HashMap::new();
-        doc_meta.insert("analysis".to_string(), json!(parent_analysis));
-
-        let _doc: DocumentNode = db.create(("document", &doc_id))
-            .content(DocumentNode { 
-                id: None, filename: original_filename.clone(), created_at: Utc::now(), metadata: doc_meta 
-            }).await.map_err(|e| e.to_string())?.expect("Failed to create doc");
-
-        // Event -> Document 연결
-        let _ = db.query("RELATE $e->imported->$d").bind(("e", session_id.clone())).bind(("d", format!("document:{}", doc_id))).await.ok();
-
-        // C. 청크 처리 (페이지 단위)
-        let chunks = pages; 
-        for (i, txt) in chunks.iter().enumerate() {
-            let chunk_uuid = Uuid::new_v4().to_string();
-            
-            print!("      Running LLM Analysis on Page #{} (Len: {})... ", i + 1, txt.len());
-            
-            // 페이지별 분석 실행
-            let chunk_res = match analyze_content(gen_url, txt).await {
-                Ok(res) => {
-                    println!("✅ Done");
-                    res
-                },
-                Err(e) => {
-                    println!("\n      ❌ ERROR: {:?}", e);
-                    CoreAnalysisResult {
-                        topic: format!("Page {}", i+1),
-                        summary: "분석 실패".to_string(),
-                        key_entities: vec![],
-                        detailed_data: json!({ "error": format!("{:?}", e) }),
-                    }
-                }
-            };
+    println!("\n✅ Ingest finished: {}", report.summary());
+    Ok(report)
+}
+
+// --- 분석에 실패한(status = failed) Chunk 다시 분석 ---
+#[tauri::command]
+pub async fn retry_failed_chunks(
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let db = &state.db;
+    let gen = state.chat_endpoint();
+    let retry = state.settings().llm.retry;
+    let categories = state.settings().llm.entity_categories;
+
+    let failed: Vec<ChunkNode> = db.query("SELECT * FROM chunk WHERE status = 'failed' LIMIT $limit")
+        .bind(("limit", limit.unwrap_or(100)))
+        .await.map_err(|e| e.to_string())?
+        .take(0).map_err(|e| e.to_string())?;
+
+    if failed.is_empty() {
+        return Ok("✨ 다시 분석할 Chunk가 없습니다.".to_string());
+    }
 
-            // Chunk 메타데이터 구성
-            let mut chunk_meta = HashMap::new();
-            chunk_meta.insert("page_number".to_string(), json!(i + 1));
-            // Step 2(Graph)를 위해 분석 데이터를 통째로 저장
-            chunk_meta.insert("analysis".to_string(), json!(chunk_res)); 
-
-            // Chunk 저장
-            let _chunk: ChunkNode = db.create(("chunk", &chunk_uuid))
-                .content(ChunkNode {
-                    id: None, 
-                    content: txt.clone(), 
-                    page_index: i, 
-                    embedding: vec![], // 임베딩은 필요 시 나중에 추가
-                    metadata: chunk_meta 
-                }).await.map_err(|e| e.to_string())?.expect("Chunk create failed");
-
-            // Document -> Chunk 연결
-            let _ = db.query("RELATE $d->contains->$c")
-                .bind(("d", format!("document:{}", doc_id)))
-                .bind(("c", format!("chunk:{}", chunk_uuid)))
-                .await.ok();
+    let total = failed.len();
+    println!("\n🔁 Retrying analysis for {} failed chunks...", total);
+
+    let mut success_count = 0;
+    for chunk in failed {
+        let Some(id) = chunk.id else { continue };
+
+        match analyze_content(&gen, &retry, &categories, &chunk.content).await {
+            Ok(res) => {
+                // 성공하면 Graph 단계(construct_graph)를 다시 거치도록 step2_processed 초기화
+                db.query("UPDATE $id SET status = 'analyzed', metadata.analysis = $analysis, metadata.analysis_error = NONE, metadata.step2_processed = false")
+                    .bind(("id", id.clone()))
+                    .bind(("analysis", res))
+                    .await.map_err(|e| e.to_string())?;
+                success_count += 1;
+            }
+            Err(e) => {
+                println!("    ❌ {}: {}", id, e);
+                db.query("UPDATE $id SET metadata.analysis_error = $error")
+                    .bind(("id", id.clone()))
+                    .bind(("error", json!(e)))
+                    .await.map_err(|e| e.to_string())?;
+            }
         }
-        success_count += 1;
     }
-    
-    Ok(format!("✅ Processed {} files with Structural Analysis.", success_count))
+
+    Ok(format!("🔁 {}/{} 개의 Chunk 재분석 성공", success_count, total))
 }
 
 // --- 2단계: Chunk 메타데이터 -> 키워드 Graph 연결 ---
+// 백그라운드 작업(job)으로 실행됩니다.
 #[tauri::command]
 pub async fn construct_graph(
-    state: State<'_, AppState>,
+    mode: Option<KeywordMode>,
+    app: AppHandle,
+) -> Result<Job, String> {
+    jobs::submit(&app, JobRequest::Graph { mode }).await
+}
+
+/// Graph 작업 본문. 처리할 Chunk가 없어질 때까지 500개씩 연결하고, Chunk 사이마다 일시정지/취소 요청을 확인합니다.
+/// (처리한 Chunk는 step2_processed로 표시되므로 재개하면 남은 것부터 이어서 진행)
+pub async fn build_graph(
+    state: &AppState,
+    ctx: &JobContext,
+    mode: Option<KeywordMode>,
 ) -> Result<String, String> {
     let db = &state.db;
+    // Entity 후보를 얻는 방식 (지정하지 않으면 설정의 keywords.mode)
+    let keyword_opts = state.settings().keywords;
+    let mode = mode.unwrap_or(keyword_opts.mode);
 
-    println!("\n🕸️ [Step 2] Building Keyword Graph (No LLM)...");
+    println!("\n🕸️ [Step 2] Building Keyword Graph (No LLM, keywords: {:?})...", mode);
 
     // 1. 아직 처리되지 않은 Chunk 조회
-    let sql = "SELECT * FROM chunk WHERE metadata.step2_processed != true LIMIT 500";
-    
-    let mut chunks_to_process: Vec<ChunkNode> = db.query(sql)
+    // Llm 모드에서는 분석에 실패한 Chunk를 재시도(retry_failed_chunks) 후에 연결하고,
+    // 나머지 모드에서는 통계 추출로 바로 연결
+    let filter = if mode == KeywordMode::Llm {
+        "metadata.step2_processed != true AND status != 'failed'"
+    } else {
+        "metadata.step2_processed != true"
+    };
+
+    let total: usize = db.query(format!("RETURN count((SELECT VALUE id FROM chunk WHERE {filter}))"))
         .await.map_err(|e| e.to_string())?
-        .take(0).map_err(|e| e.to_string())?;
+        .take::<Option<usize>>(0).map_err(|e| e.to_string())?
+        .unwrap_or(0);
 
-    if chunks_to_process.is_empty() {
+    if total == 0 {
         return Ok("✨ 처리할 새로운 Chunk가 없습니다.".to_string());
     }
 
-    let total = chunks_to_process.len();
     println!(" 🚀 Linking {} chunks based on tags/keywords...", total);
 
+    let mut processed = 0;
     let mut success_count = 0;
+    // TF-IDF용 문서 빈도 (통계 추출이 필요한 Chunk가 있을 때만 전체 Chunk로 계산)
+    let mut corpus: Option<Corpus> = None;
+
+    // 처리한 Chunk는 조건에서 빠지므로 매번 앞에서부터 다시 조회 (새로 Ingest된 Chunk는 다음 작업에서)
+    while processed < total {
+        let chunks_to_process: Vec<ChunkNode> = db.query(format!("SELECT * FROM chunk WHERE {filter} LIMIT 500"))
+            .await.map_err(|e| e.to_string())?
+            .take(0).map_err(|e| e.to_string())?;
+        if chunks_to_process.is_empty() { break; }
+
+        for chunk in chunks_to_process.iter() {
+            ctx.checkpoint()?;
+            if processed % 20 == 0 {
+                ctx.progress(processed, total, format!("{}/{} chunks", processed, total)).await;
+            }
+            processed += 1;
+
+            let chunk_thing = match &chunk.id {
+                Some(t) => t.clone(),
+                None => continue,
+            };
 
-    for chunk in chunks_to_process.iter() {
-        let chunk_thing = match &chunk.id {
-            Some(t) => t.clone(),
-            None => continue,
-        };
-
-        // 2. 메타데이터에서 키워드 수집 (중복 제거)
-        let mut topics: HashSet<String> = HashSet::new();
-
-        // 참고: analyze_content의 결과가 metadata["analysis"]에 들어있다고 가정
-        // 직접적인 "tags"나 "keywords" 필드가 없다면 아래 로직은 빈 동작을 할 수 있음.
-        // 필요 시 chunk.metadata["analysis"]["key_entities"] 등을 파싱하도록 수정 가능.
-        
-        // (1) Tags 탐색
-        if let Some(tags_val) = chunk.metadata.get("tags") {
-            if let Some(arr) = tags_val.as_array() {
-                for t in arr {
-                    if let Some(s) = t.as_str() { topics.insert(s.trim().to_string()); }
+            // 2. 메타데이터에서 Entity 수집 (정규화한 이름 기준 중복 제거)
+            // 정규화한 이름 -> (분류, 설명, 원래 표기들)
+            let mut topics: HashMap<String, (String, String, Vec<String>)> = HashMap::new();
+            let mut add_topic = |surface: &str, category: String, description: String, overwrite: bool| {
+                let name = normalize_entity_name(surface);
+                if name.is_empty() { return; }
+                let entry = topics.entry(name.clone())
+                    .or_insert_with(|| (category.clone(), description.clone(), Vec::new()));
+                if overwrite {
+                    entry.0 = category;
+                    if !description.is_empty() { entry.1 = description; }
                 }
-            }
-        }
+                // "삼성전자는" 처럼 정규화로 바뀐 표기는 별칭으로 보관
+                let surface = surface.trim().to_string();
+                if surface != name && !entry.2.contains(&surface) {
+                    entry.2.push(surface);
+                }
+            };
 
-        // (2) Keywords 탐색
-        if let Some(kws_val) = chunk.metadata.get("keywords") {
-            if let Some(arr) = kws_val.as_array() {
-                for k in arr {
-                    if let Some(s) = k.as_str() { topics.insert(s.trim().to_string()); }
+            // (1) Tags / Keywords: 분류 정보가 없으므로 "Keyword"
+            for key in ["tags", "keywords"] {
+                if let Some(arr) = chunk.metadata.get(key).and_then(|v| v.as_array()) {
+                    for t in arr {
+                        if let Some(s) = t.as_str() {
+                            add_topic(s, "Keyword".to_string(), String::new(), false);
+                        }
+                    }
                 }
             }
-        }
-        
-        // (3) Analysis 결과 내 key_entities 탐색 (추가 보완)
-        if let Some(analysis_val) = chunk.metadata.get("analysis") {
-            if let Some(entities) = analysis_val.get("key_entities").and_then(|v| v.as_array()) {
-                for e in entities {
-                    if let Some(s) = e.as_str() { topics.insert(s.trim().to_string()); }
+
+            // (2) Analysis 결과의 key_entities (분류/설명이 있으면 우선)
+            let llm_entities = match mode {
+                KeywordMode::Statistical => vec![],
+                _ => chunk.metadata.get("analysis").map(key_entities_from).unwrap_or_default(),
+            };
+            let use_statistical = match mode {
+                KeywordMode::Llm => false,
+                KeywordMode::Fallback => llm_entities.is_empty(),
+                KeywordMode::Statistical => true,
+            };
+            for e in llm_entities {
+                add_topic(&e.name, e.entity_type, e.description, true);
+            }
+
+            // (3) 통계 추출 (TF-IDF + TextRank): LLM 결과가 없거나 Statistical 모드일 때
+            let mut statistical_terms: Vec<String> = vec![];
+            if use_statistical {
+                if corpus.is_none() {
+                    let contents: Vec<String> = db.query("SELECT VALUE content FROM chunk")
+                        .await.map_err(|e| e.to_string())?
+                        .take(0).map_err(|e| e.to_string())?;
+                    corpus = Some(Corpus::build(contents.iter().map(String::as_str)));
+                }
+                if let Some(corpus) = &corpus {
+                    for k in extract_keywords(&chunk.content, corpus, &keyword_opts) {
+                        add_topic(&k.term, "Keyword".to_string(), String::new(), false);
+                        statistical_terms.push(k.term);
+                    }
                 }
             }
-        }
 
-        // 3. Entity 생성 및 연결
-        for topic in topics {
-            if topic.is_empty() { continue; }
-
-            let safe_name = crate::utils::sanitize_id(&topic);
-            let entity_id = Thing::from(("entity", safe_name.as_str()));
-
-            // Entity Upsert
-            let _: Option<EntityNode> = db
-                .upsert(("entity", &safe_name))
-                .content(EntityNode {
-                    id: Some(entity_id.clone()),
-                    name: topic.clone(),
-                    category: "Keyword".to_string(),
-                    description: format!("Extracted keyword: {}", topic),
-                    embedding: vec![],
-                    created_at: Utc::now(),
-                })
-                .await.ok().flatten();
-
-            // Chunk -> mentions -> Entity 연결
-            let sql = "RELATE $c -> mentions -> $e";
-            let _ = db.query(sql)
+            // 3. Entity 생성 및 연결
+            // 다시 연결하는 Chunk(retry_failed_chunks 후 등)는 이전 mentions를 지우고 새로 만듦
+            // (재분석 전에 통계 추출로 붙은 키워드가 남거나 Edge가 중복되지 않도록)
+            if let Err(e) = db.query("DELETE mentions WHERE in = $c")
                 .bind(("c", chunk_thing.clone()))
-                .bind(("e", entity_id))
-                .await.ok();
-        }
+                .await.and_then(surrealdb::Response::check)
+            {
+                println!("    ⚠️ Old mentions cleanup failed ({}): {}", chunk_thing, e);
+            }
+            for (topic, (category, description, aliases)) in topics {
+                // 다른 Entity에 병합된 이름이면 살아남은 Entity에 연결
+                let entity_id = match resolve_alias(db, &topic).await {
+                    Ok(Some(id)) => id,
+                    _ => Thing::from(("entity", crate::utils::sanitize_id(&topic).as_str())),
+                };
+
+                // Entity Upsert: 이름/임베딩은 보존하고, 설명은 새로 얻은 값이 있을 때만 갱신
+                // (이미 구체적인 분류가 있으면 "Keyword"/"Other"로 덮어쓰지 않음)
+                let sql = "
+                    UPSERT $id SET
+                        name = name ?? $name,
+                        category = IF category != NONE AND $category IN ['Keyword', 'Other'] THEN category ELSE $category END,
+                        description = IF $description != '' THEN $description ELSE description ?? '' END,
+                        aliases = array::union(aliases ?? [], $aliases),
+                        created_at = created_at ?? time::now()
+                ";
+                if let Err(e) = db.query(sql)
+                    .bind(("id", entity_id.clone()))
+                    .bind(("name", topic.clone()))
+                    .bind(("category", category))
+                    .bind(("description", description))
+                    .bind(("aliases", aliases))
+                    .await.and_then(surrealdb::Response::check)
+                {
+                    println!("    ⚠️ Entity upsert failed ({}): {}", topic, e);
+                    continue;
+                }
 
-        // 4. 처리 완료 마킹
-        let _: Option<ChunkNode> = db.update(("chunk", chunk_thing.id.to_string()))
-            .merge(json!({
-                "metadata": { "step2_processed": true }
-            }))
-            .await.ok().flatten();
+                // Chunk -> mentions -> Entity 연결
+                let sql = "RELATE $c -> mentions -> $e";
+                let _ = db.query(sql)
+                    .bind(("c", chunk_thing.clone()))
+                    .bind(("e", entity_id))
+                    .await.ok();
+            }
+
+            // 4. 처리 완료 마킹 (통계 추출한 키워드는 LLM 태그와 섞이지 않도록 따로 기록)
+            let marker = json!({
+                "step2_processed": true,
+                "statistical_keywords": if statistical_terms.is_empty() { JsonValue::Null } else { json!(statistical_terms) },
+            });
+            // 기록하지 못하면 다음 조회에서 같은 Chunk를 다시 읽게 되므로 작업을 멈춤
+            db.query("UPDATE $c MERGE $patch")
+                .bind(("c", chunk_thing.clone()))
+                .bind(("patch", json!({ "metadata": marker })))
+                .await.and_then(surrealdb::Response::check)
+                .map_err(|e| format!("Chunk {} 처리 완료 기록 실패: {}", chunk_thing, e))?;
 
-        success_count += 1;
+            success_count += 1;
+        }
     }
 
+    ctx.progress(processed, total, format!("{}/{} chunks", processed, total)).await;
     Ok(format!("✅ {}/{} 개의 청크 연결 완료 (고속 모드)", success_count, total))
 }
 
@@ -266,7 +339,7 @@ pub async fn get_documents(state: State<'_, AppState>) -> Result<Vec<DocumentWit
     let sql = "
         SELECT 
             *, 
-            (SELECT * FROM ->contains->chunk ORDER BY page_index ASC) AS chunks 
+            (SELECT * FROM ->contains->chunk ORDER BY page_index ASC, metadata.chunk_index ASC) AS chunks 
         FROM document 
         ORDER BY created_at DESC
     ";
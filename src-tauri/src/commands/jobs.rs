@@ -0,0 +1,36 @@
+use tauri::{AppHandle, State};
+
+use crate::jobs::{self, Job, JobStatus};
+use crate::AppState;
+
+// --- 작업 목록 (최근 순, status로 거를 수 있음) ---
+#[tauri::command]
+pub async fn list_jobs(
+    status: Option<JobStatus>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Job>, String> {
+    jobs::list_jobs(&state.db, status, limit.unwrap_or(50)).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_job(id: String, state: State<'_, AppState>) -> Result<Job, String> {
+    jobs::get_job(&state.db, &id).await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Job not found: {}", id))
+}
+
+#[tauri::command]
+pub async fn cancel_job(id: String, app: AppHandle) -> Result<Job, String> {
+    jobs::cancel(&app, &id).await
+}
+
+#[tauri::command]
+pub async fn pause_job(id: String, app: AppHandle) -> Result<Job, String> {
+    jobs::pause(&app, &id).await
+}
+
+#[tauri::command]
+pub async fn resume_job(id: String, app: AppHandle) -> Result<Job, String> {
+    jobs::resume(&app, &id).await
+}
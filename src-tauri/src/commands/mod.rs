@@ -4,6 +4,15 @@
 pub mod ingest;
 pub mod query;
 pub mod log;
+pub mod embed;
+pub mod search;
+pub mod chat;
+pub mod conversation;
+pub mod settings;
+pub mod relations;
+pub mod resolution;
+pub mod watch;
+pub mod jobs;
 
 // (선택) 밖에서 crate::commands::process_pdfs 처럼 바로 쓰게 하려면:
 // pub use ingest::process_pdfs;
\ No newline at end of file
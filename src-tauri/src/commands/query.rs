@@ -58,18 +58,40 @@ pub async fn fetch_graph_data(
     for d in docs_res {
         let id = get_str(&d, "id");
         let filename = get_str(&d, "filename");
+        let relative_path = get_str(&d, "relative_path");
         
         if !id.is_empty() {
             nodes.push(GraphNodeRes {
                 id,
                 group: "document".into(),
                 label: if filename.is_empty() { "Untitled".into() } else { filename },
-                info: Some("Original PDF Document".into()),
+                info: Some(if relative_path.is_empty() { "Original Document".into() } else { relative_path }),
                 val: 20.0,
             });
         }
     }
 
+    // 1-1. Folders 조회 (폴더 -> contains -> 하위 폴더/문서)
+    if view_mode != "semantic" {
+        let sql_folder = "SELECT *, type::string(id) as id FROM folder";
+        let folders_res: Vec<JsonValue> = db.query(sql_folder)
+            .await.map_err(|e| e.to_string())?
+            .take(0).map_err(|e| e.to_string())?;
+
+        for f in folders_res {
+            let id = get_str(&f, "id");
+            if id.is_empty() { continue; }
+
+            nodes.push(GraphNodeRes {
+                id,
+                group: "folder".into(),
+                label: get_str(&f, "name"),
+                info: Some(get_str(&f, "path")),
+                val: 15.0,
+            });
+        }
+    }
+
     // 2. Chunks 조회
     if view_mode != "semantic" {
         let sql_chunk = "SELECT *, type::string(id) as id FROM chunk";
@@ -121,7 +143,8 @@ pub async fn fetch_graph_data(
             id,
             group: "entity".into(),
             label: name,
-            info: Some(format!("[{}] {}", category, desc)),
+            // 설명이 없는 Entity(이전 데이터, 관계에서만 등장)는 분류만 표시
+            info: Some(if desc.is_empty() { format!("[{}]", category) } else { format!("[{}] {}", category, desc) }),
             val: 10.0,
         });
     }
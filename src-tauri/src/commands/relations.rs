@@ -0,0 +1,157 @@
+use tauri::State;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use surrealdb::engine::local::Db;
+use surrealdb::sql::Thing;
+use surrealdb::Surreal;
+
+use crate::llm::extractor::key_entities_from;
+use crate::llm::relations::extract_relations;
+use crate::resolution::merge::resolve_alias;
+use crate::resolution::normalize::normalize_entity_name;
+use crate::utils::sanitize_id;
+use crate::AppState;
+
+/// 이보다 confidence가 낮은 관계는 저장하지 않음
+const DEFAULT_MIN_CONFIDENCE: f32 = 0.5;
+
+/// 관계 추출에 이만큼 실패한 Chunk는 더 이상 시도하지 않음 (원인은 metadata.relations_error)
+const MAX_RELATION_ATTEMPTS: usize = 3;
+
+#[derive(Debug, Deserialize)]
+struct PendingChunk {
+    id: Thing,
+    content: String,
+    #[serde(default)]
+    metadata: HashMap<String, JsonValue>,
+}
+
+// --- 3단계: Chunk별 관계 추출 (LLM) -> entity -> related_to -> entity ---
+#[tauri::command]
+pub async fn extract_chunk_relations(
+    limit: Option<usize>,
+    min_confidence: Option<f32>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let db = &state.db;
+    let gen = state.chat_endpoint();
+    let retry = state.settings().llm.retry;
+    let min_confidence = min_confidence.unwrap_or(DEFAULT_MIN_CONFIDENCE);
+
+    println!("\n🔗 [Step 3] Extracting Relations (LLM)...");
+
+    // 분석에 성공했고 아직 관계 추출을 하지 않은 Chunk
+    // (실패한 적이 적은 Chunk부터: 계속 실패하는 Chunk가 새 Chunk를 밀어내지 않도록)
+    let chunks: Vec<PendingChunk> = db
+        .query("
+            SELECT id, content, metadata, metadata.relations_attempts ?? 0 AS attempts FROM chunk
+            WHERE status != 'failed' AND metadata.relations_processed != true AND (metadata.relations_attempts ?? 0) < $max_attempts
+            ORDER BY attempts ASC
+            LIMIT $limit
+        ")
+        .bind(("max_attempts", MAX_RELATION_ATTEMPTS))
+        .bind(("limit", limit.unwrap_or(100)))
+        .await.map_err(|e| e.to_string())?
+        .take(0).map_err(|e| e.to_string())?;
+
+    if chunks.is_empty() {
+        return Ok("✨ 관계를 추출할 새로운 Chunk가 없습니다.".to_string());
+    }
+
+    let total = chunks.len();
+    let mut relation_count = 0;
+    let mut failed_count = 0;
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let known_entities: Vec<String> = chunk.metadata.get("analysis")
+            .map(|a| key_entities_from(a).into_iter().map(|e| e.name).collect())
+            .unwrap_or_default();
+
+        print!("    [{}/{}] {} ... ", i + 1, total, chunk.id);
+        let triples = match extract_relations(&gen, &retry, &chunk.content, &known_entities).await {
+            Ok(t) => t,
+            Err(e) => {
+                // 실패 원인과 횟수를 기록하고 다음 실행에서 다시 시도 (MAX_RELATION_ATTEMPTS번까지)
+                println!("❌ {}", e);
+                db.query("UPDATE $id SET metadata.relations_error = $error, metadata.relations_attempts = (metadata.relations_attempts ?? 0) + 1")
+                    .bind(("id", chunk.id.clone()))
+                    .bind(("error", e))
+                    .await.map_err(|e| e.to_string())?;
+                failed_count += 1;
+                continue;
+            }
+        };
+
+        let mut saved = 0;
+        for t in triples {
+            let (subject, predicate, object) = (t.subject.trim(), t.predicate.trim(), t.object.trim());
+            let confidence = t.confidence.clamp(0.0, 1.0);
+            if predicate.is_empty() || confidence < min_confidence { continue; }
+
+            // 조사/기호만 있던 이름은 정규화하면 비므로 건너뜀, 자기 자신과의 관계도 저장하지 않음
+            let (subject_name, object_name) = (normalize_entity_name(subject), normalize_entity_name(object));
+            if !is_valid_entity_name(&subject_name) || !is_valid_entity_name(&object_name) { continue; }
+            if sanitize_id(&subject_name) == sanitize_id(&object_name) { continue; }
+
+            let s = ensure_entity(db, subject, &subject_name).await.map_err(|e| e.to_string())?;
+            let o = ensure_entity(db, object, &object_name).await.map_err(|e| e.to_string())?;
+            if s == o { continue; }
+
+            // 근거(evidence)로 관계를 추출한 Chunk ID를 함께 저장
+            db.query("RELATE $s->related_to->$o SET relation = $relation, confidence = $confidence, evidence = $chunk, created_at = time::now()")
+                .bind(("s", s))
+                .bind(("o", o))
+                .bind(("relation", predicate.to_string()))
+                .bind(("confidence", confidence))
+                .bind(("chunk", chunk.id.clone()))
+                .await.map_err(|e| e.to_string())?
+                .check().map_err(|e| e.to_string())?;
+            saved += 1;
+        }
+        println!("✅ {} relations", saved);
+        relation_count += saved;
+
+        db.query("UPDATE $id SET metadata.relations_processed = true, metadata.relation_count = $count, metadata.relations_error = NONE")
+            .bind(("id", chunk.id.clone()))
+            .bind(("count", saved))
+            .await.map_err(|e| e.to_string())?;
+    }
+
+    Ok(format!(
+        "✅ {}개의 Chunk에서 {}개의 관계 저장 (실패 {}개)",
+        total - failed_count, relation_count, failed_count
+    ))
+}
+
+/// 정규화한 이름에 글자/숫자가 하나라도 있는지 (없으면 ID가 "_"뿐인 Entity가 생김)
+fn is_valid_entity_name(name: &str) -> bool {
+    name.chars().any(char::is_alphanumeric)
+}
+
+/// 정규화한 이름(`name`)에 해당하는 Entity를 찾고, 없으면 새로 만듭니다. (construct_graph와 같은 ID 규칙)
+/// 이미 있는 Entity는 분류/설명/임베딩을 보존하고, 원래 표기(`surface`)만 별칭에 더함
+async fn ensure_entity(db: &Surreal<Db>, surface: &str, name: &str) -> surrealdb::Result<Thing> {
+    // 다른 Entity에 병합된 이름이면 살아남은 Entity를 사용
+    let id = match resolve_alias(db, name).await? {
+        Some(id) => id,
+        None => Thing::from(("entity", sanitize_id(name).as_str())),
+    };
+
+    // 관계에서만 등장한 Entity는 분류/설명을 알 수 없음 (construct_graph에서 채워짐)
+    let aliases: Vec<String> = if surface != name { vec![surface.to_string()] } else { vec![] };
+    db.query("
+        UPSERT $id SET
+            name = name ?? $name,
+            category = category ?? 'Other',
+            description = description ?? '',
+            aliases = array::union(aliases ?? [], $aliases),
+            created_at = created_at ?? time::now()
+    ")
+        .bind(("id", id.clone()))
+        .bind(("name", name.to_string()))
+        .bind(("aliases", aliases))
+        .await?.check()?;
+
+    Ok(id)
+}
@@ -0,0 +1,67 @@
+use tauri::State;
+
+use crate::resolution::{find_candidates, merge_candidates, parse_entity_id, MergeCandidate, ResolutionOptions};
+use crate::resolution::merge::merge_into;
+use crate::AppState;
+
+// --- 같은 대상을 가리키는 것으로 보이는 Entity 쌍 조회 ---
+#[tauri::command]
+pub async fn find_merge_candidates(
+    options: Option<ResolutionOptions>,
+    state: State<'_, AppState>,
+) -> Result<Vec<MergeCandidate>, String> {
+    // 지정하지 않으면 설정(resolution.*)을 따름
+    let opts = options.unwrap_or_else(|| state.settings().resolution);
+    let candidates = find_candidates(&state.db, &opts).await.map_err(|e| e.to_string())?;
+
+    println!("🧩 find_merge_candidates -> {} candidates (min score {:.2})", candidates.len(), opts.min_score);
+    Ok(candidates)
+}
+
+// --- 임계값 이상인 후보를 자동 병합 ---
+#[tauri::command]
+pub async fn auto_merge_entities(
+    threshold: Option<f32>,
+    options: Option<ResolutionOptions>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let opts = options.unwrap_or_else(|| state.settings().resolution);
+    let threshold = threshold.unwrap_or(opts.auto_merge_threshold);
+
+    println!("\n🧩 Auto-merging entities (threshold {:.2})...", threshold);
+    let candidates: Vec<MergeCandidate> = find_candidates(&state.db, &opts).await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        // 이름만 비슷한 쌍은 사용자가 확인하도록 남김
+        .filter(|c| c.score >= threshold && c.signals.supports_auto_merge())
+        .collect();
+
+    if candidates.is_empty() {
+        return Ok("✨ 병합할 Entity가 없습니다.".to_string());
+    }
+
+    let merged = merge_candidates(&state.db, &candidates).await.map_err(|e| e.to_string())?;
+    Ok(format!("✅ {}개의 Entity 병합 완료", merged))
+}
+
+// --- 수동 병합: merge_ids의 Entity들을 keep_id에 합침 ---
+#[tauri::command]
+pub async fn merge_entities(
+    keep_id: String,
+    merge_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let keep = parse_entity_id(&keep_id).map_err(|e| e.to_string())?;
+
+    let mut merged = 0;
+    for id in &merge_ids {
+        let merge = parse_entity_id(id).map_err(|e| e.to_string())?;
+        if merge == keep { continue; }
+
+        merge_into(&state.db, &keep, &merge).await.map_err(|e| e.to_string())?;
+        println!("    🔀 Merged {} -> {}", merge, keep);
+        merged += 1;
+    }
+
+    Ok(format!("✅ {}개의 Entity를 {}에 병합", merged, keep_id))
+}
@@ -0,0 +1,69 @@
+use tauri::State;
+
+use crate::commands::query::GraphResponse;
+use crate::llm::embedder::embed_query;
+use crate::models::RetrievedChunk;
+use crate::retrieval::graph::{graph_search, GraphRetrieval, GraphSearchOptions};
+use crate::retrieval::filter::RetrievalFilter;
+use crate::retrieval::hybrid::HybridWeights;
+use crate::retrieval::rerank::RerankOptions;
+use crate::retrieval::{retrieve, RetrievalOptions, SearchMode};
+use crate::AppState;
+
+// --- 질문과 관련된 Chunk 검색 (Vector / BM25 / Hybrid, 선택적 리랭킹) ---
+#[tauri::command]
+pub async fn search_docs(
+    query: String,
+    top_k: Option<usize>,
+    mode: Option<SearchMode>,
+    weights: Option<HybridWeights>,
+    rerank: Option<RerankOptions>,
+    filter: Option<RetrievalFilter>,
+    state: State<'_, AppState>,
+) -> Result<Vec<RetrievedChunk>, String> {
+    let query = query.trim();
+    if query.is_empty() { return Ok(vec![]); }
+
+    // 지정하지 않은 값은 설정(retrieval.*)을 따름
+    let defaults = state.settings().retrieval;
+    let opts = RetrievalOptions {
+        top_k: top_k.unwrap_or(defaults.top_k),
+        mode: mode.unwrap_or_default(),
+        weights: weights.unwrap_or_default(),
+        rerank: rerank.unwrap_or(defaults.rerank),
+        filter: filter.unwrap_or_default(),
+    };
+    let results = retrieve(&state, query, &opts).await.map_err(|e| e.to_string())?;
+
+    println!("🔎 search_docs ({:?}): \"{}\" -> {} results", opts.mode, query, results.len());
+    Ok(results)
+}
+
+// --- Graph-RAG: 관련 Entity에서 mentions/related_to Edge를 따라 Chunk 수집 ---
+#[tauri::command]
+pub async fn search_graph(
+    query: String,
+    options: Option<GraphSearchOptions>,
+    state: State<'_, AppState>,
+) -> Result<GraphRetrieval, String> {
+    let query = query.trim();
+    let opts = options.unwrap_or_default();
+
+    if query.is_empty() {
+        return Ok(GraphRetrieval { chunks: vec![], evidence: vec![], subgraph: GraphResponse { nodes: vec![], links: vec![] } });
+    }
+
+    // 임베딩 서버가 꺼져 있어도 이름 매칭만으로 탐색할 수 있도록 실패는 무시
+    let query_vec = embed_query(&state.embed_client(), query).await.unwrap_or_else(|e| {
+        println!("⚠️ search_graph: embedding failed ({}), falling back to name matching", e);
+        vec![]
+    });
+
+    let result = graph_search(&state.db, &state.entity_names, query, query_vec, &opts).await.map_err(|e| e.to_string())?;
+
+    println!(
+        "🕸️ search_graph: \"{}\" -> {} chunks, {} nodes walked",
+        query, result.chunks.len(), result.subgraph.nodes.len()
+    );
+    Ok(result)
+}
@@ -0,0 +1,48 @@
+use tauri::{AppHandle, Manager, State};
+
+use crate::config::AppConfig;
+use crate::{start_servers, AppState};
+
+// --- 현재 설정 조회 ---
+#[tauri::command]
+pub async fn get_settings(state: State<'_, AppState>) -> Result<AppConfig, String> {
+    Ok(state.settings())
+}
+
+// --- 시작할 때 crisper.toml을 읽지 못했으면 그 원인 (update_settings로 저장하면 해제) ---
+#[tauri::command]
+pub async fn get_settings_error(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    Ok(state.config_error.read().unwrap().clone())
+}
+
+// --- 설정 변경: 검증 후 crisper.toml에 저장하고 바로 적용 ---
+// 서버 설정(servers.*)이나 채팅 모델명이 바뀌면 llama-server를 재시작합니다.
+// database.path는 앱을 다시 실행해야 적용됩니다.
+#[tauri::command]
+pub async fn update_settings(app: AppHandle, settings: AppConfig) -> Result<AppConfig, String> {
+    settings.validate()?;
+
+    let restart = {
+        let state = app.state::<AppState>();
+        let current = state.settings();
+
+        settings.save(&state.config_dir).map_err(|e| e.to_string())?;
+        if current.database.path != settings.database.path {
+            println!("⚙️ database.path changed, restart the app to apply: {}", settings.database.path);
+        }
+
+        // 설정 오류로 서버를 띄우지 않았다면 이제 시작
+        let had_error = state.config_error.write().unwrap().take().is_some();
+        let restart = had_error || current.servers != settings.servers || current.llm.chat_model != settings.llm.chat_model;
+        state.apply_settings(settings.clone());
+        restart
+    };
+
+    if restart {
+        println!("⚙️ Server settings changed, restarting llama-server...");
+        start_servers(&app).await;
+    }
+
+    println!("⚙️ Settings updated");
+    Ok(settings)
+}
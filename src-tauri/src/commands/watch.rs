@@ -0,0 +1,71 @@
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use tauri::State;
+use uuid::Uuid;
+
+use crate::ingest::source_path_of;
+use crate::models::WatchedFolder;
+use crate::watcher::list_folders;
+use crate::AppState;
+
+// --- 감시 폴더 등록 (새 파일/바뀐 파일은 자동 Ingest, 지운 파일은 문서도 삭제) ---
+#[tauri::command]
+pub async fn add_watched_folder(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<WatchedFolder, String> {
+    if !Path::new(&path).is_dir() {
+        return Err(format!("Not a directory: {}", path));
+    }
+    let path = source_path_of(Path::new(&path));
+
+    let existing: Vec<WatchedFolder> = state.db
+        .query("SELECT * FROM watched_folder WHERE path = $path")
+        .bind(("path", path.clone()))
+        .await.map_err(|e| e.to_string())?
+        .take(0).map_err(|e| e.to_string())?;
+
+    let folder = match existing.into_iter().next() {
+        Some(folder) => folder,
+        None => {
+            let id = Uuid::new_v4().to_string();
+            let created: Option<WatchedFolder> = state.db.create(("watched_folder", id.as_str()))
+                .content(WatchedFolder { id: None, path: path.clone(), created_at: Utc::now(), last_synced_at: None })
+                .await.map_err(|e| e.to_string())?;
+            created.ok_or("Watched folder create failed")?
+        }
+    };
+
+    // 감시 시작 + 현재 폴더 내용 동기화 (백그라운드에서 처리)
+    let mut watcher = state.watcher.lock().unwrap();
+    let watcher = watcher.as_mut().ok_or("Folder watcher is not running")?;
+    watcher.watch(&PathBuf::from(&path))?;
+
+    println!("👀 Watching: {}", path);
+    Ok(folder)
+}
+
+// --- 감시 해제 (이미 저장된 문서는 그대로 둠) ---
+#[tauri::command]
+pub async fn remove_watched_folder(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let path = source_path_of(Path::new(&path));
+
+    if let Some(watcher) = state.watcher.lock().unwrap().as_mut() {
+        watcher.unwatch(&PathBuf::from(&path));
+    }
+    state.db.query("DELETE watched_folder WHERE path = $path")
+        .bind(("path", path.clone()))
+        .await.map_err(|e| e.to_string())?
+        .check().map_err(|e| e.to_string())?;
+
+    println!("🙈 Stopped watching: {}", path);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_watched_folders(state: State<'_, AppState>) -> Result<Vec<WatchedFolder>, String> {
+    list_folders(&state.db).await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,300 @@
+// src-tauri/src/config.rs
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::chunker::ChunkerOptions;
+use crate::ingest::scan::{validate_patterns, ScanOptions};
+use crate::jobs::JobOptions;
+use crate::keywords::KeywordOptions;
+use crate::llm::structured::RetryPolicy;
+use crate::resolution::ResolutionOptions;
+use crate::retrieval::rerank::RerankOptions;
+
+/// tauri.conf.json의 identifier (앱 설정 폴더 이름)
+const APP_IDENTIFIER: &str = "com.crisper.app";
+const CONFIG_FILE_NAME: &str = "crisper.toml";
+
+/// 앱 전체 설정
+/// 적용 순서: 기본값 -> 앱 설정 폴더의 crisper.toml -> 환경변수(CRISPER_*)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct AppConfig {
+    pub database: DatabaseConfig,
+    pub servers: ServersConfig,
+    pub llm: LlmConfig,
+    pub chunking: ChunkerOptions,
+    pub retrieval: RetrievalConfig,
+    pub resolution: ResolutionOptions,
+    pub keywords: KeywordOptions,
+    /// 폴더 Ingest 시 파일 선택 규칙
+    pub ingest: ScanOptions,
+    /// 백그라운드 작업 실행 설정
+    pub jobs: JobOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    /// RocksDB 데이터 폴더 (상대 경로는 실행 위치 기준)
+    pub path: String,
+}
+
+/// llama-server 사이드카 설정
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ServersConfig {
+    pub host: String,
+    pub use_gpu: bool,
+    pub embed: ServerConfig,
+    pub chat: ServerConfig,
+    pub rerank: ServerConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ServerConfig {
+    pub enabled: bool,
+    pub model_path: String,
+    pub port: u16,
+    pub ctx_size: u32,
+    pub batch_size: u32,
+    pub parallel: u32,
+    /// GPU 모드일 때 올릴 레이어 수 (CPU 모드에서는 항상 0)
+    pub gpu_layers: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LlmConfig {
+    /// 채팅 서버의 --alias 이자 요청에 넣는 모델명
+    pub chat_model: String,
+    /// 구조화 추출(analyze_content) 재시도 정책
+    pub retry: RetryPolicy,
+    /// Entity 분류 목록 (분석 시 key_entities의 type은 이 중 하나 또는 "Other"로 제한됨)
+    pub entity_categories: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RetrievalConfig {
+    pub top_k: usize,
+    pub rerank: RerankOptions,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self { path: "../data/crisper_db".to_string() }
+    }
+}
+
+impl Default for ServersConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            use_gpu: false,
+            embed: ServerConfig {
+                enabled: true,
+                model_path: "C:/eoraha/crisper_app/crisper-app/src-tauri/models/ggml-model-Q4_K_M.gguf".to_string(),
+                port: 8080,
+                ctx_size: 2048,
+                batch_size: 2048,
+                parallel: 1,
+                gpu_layers: 99,
+            },
+            chat: ServerConfig {
+                enabled: true,
+                model_path: "C:/eoraha/crisper_app/crisper-app/src-tauri/models/qwen2.5-7b-instruct-q2_k.gguf".to_string(),
+                port: 8081,
+                ctx_size: 4096,
+                batch_size: 2048,
+                parallel: 2,
+                gpu_layers: 99,
+            },
+            // 리랭킹은 선택 단계이고 모델도 함께 배포하지 않으므로 기본은 끔
+            rerank: ServerConfig {
+                enabled: false,
+                model_path: "C:/eoraha/crisper_app/crisper-app/src-tauri/models/bge-reranker-v2-m3-Q4_K_M.gguf".to_string(),
+                port: 8082,
+                ctx_size: 2048,
+                batch_size: 2048,
+                parallel: 1,
+                gpu_layers: 99,
+            },
+        }
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self { enabled: true, model_path: String::new(), port: 0, ctx_size: 2048, batch_size: 2048, parallel: 1, gpu_layers: 99 }
+    }
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            chat_model: "gpt-3.5-turbo".to_string(),
+            retry: RetryPolicy::default(),
+            entity_categories: ["Person", "Organization", "Location", "Technology", "Product", "Event", "Concept"]
+                .iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl Default for RetrievalConfig {
+    fn default() -> Self {
+        Self { top_k: 5, rerank: RerankOptions::default() }
+    }
+}
+
+/// 앱 설정 폴더 (Tauri의 app_config_dir과 동일한 위치)
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir().unwrap_or_else(|| PathBuf::from(".")).join(APP_IDENTIFIER)
+}
+
+impl AppConfig {
+    /// 기본값 -> TOML 파일 -> 환경변수 순서로 설정을 읽고 검증합니다.
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let mut config = Self::read_file(dir)?;
+        config.apply_env();
+        config.validate().map_err(|e| anyhow::anyhow!(e))?;
+        Ok(config)
+    }
+
+    /// 기본값 -> TOML 파일까지만 적용한 설정 (환경변수 제외)
+    fn read_file(dir: &Path) -> anyhow::Result<Self> {
+        let path = dir.join(CONFIG_FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(&path)?;
+        toml::from_str(&text).map_err(|e| anyhow::anyhow!("{:?}: {}", path, e))
+    }
+
+    /// TOML 파일로 저장합니다.
+    /// 환경변수로 덮어쓴 항목은 파일에 있던 값을 그대로 두어, 환경변수 값이 파일에 굳지 않게 함
+    pub fn save(&self, dir: &Path) -> anyhow::Result<()> {
+        // 파일이 깨져 있으면 (설정 화면에서 고쳐 저장하는 경우) 기본값을 기준으로 비교
+        let file = Self::read_file(dir).unwrap_or_default();
+        let mut with_env = file.clone();
+        with_env.apply_env();
+
+        let mut merged = serde_json::to_value(self)?;
+        keep_file_values(&mut merged, &serde_json::to_value(&file)?, &serde_json::to_value(&with_env)?);
+        let to_save: Self = serde_json::from_value(merged)?;
+
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(CONFIG_FILE_NAME), toml::to_string_pretty(&to_save)?)?;
+        Ok(())
+    }
+
+    /// CRISPER_* 환경변수로 설정을 덮어씁니다.
+    fn apply_env(&mut self) {
+        set_from_env("CRISPER_DB_PATH", &mut self.database.path);
+        set_from_env("CRISPER_SERVER_HOST", &mut self.servers.host);
+        set_from_env("CRISPER_USE_GPU", &mut self.servers.use_gpu);
+        set_from_env("CRISPER_CHAT_MODEL", &mut self.llm.chat_model);
+        set_from_env("CRISPER_TOP_K", &mut self.retrieval.top_k);
+        set_from_env("CRISPER_CHUNK_MAX_TOKENS", &mut self.chunking.max_tokens);
+        set_from_env("CRISPER_CHUNK_OVERLAP_TOKENS", &mut self.chunking.overlap_tokens);
+
+        for (prefix, server) in [
+            ("EMBED", &mut self.servers.embed),
+            ("CHAT", &mut self.servers.chat),
+            ("RERANK", &mut self.servers.rerank),
+        ] {
+            set_from_env(&format!("CRISPER_{prefix}_ENABLED"), &mut server.enabled);
+            set_from_env(&format!("CRISPER_{prefix}_MODEL_PATH"), &mut server.model_path);
+            set_from_env(&format!("CRISPER_{prefix}_PORT"), &mut server.port);
+            set_from_env(&format!("CRISPER_{prefix}_CTX_SIZE"), &mut server.ctx_size);
+            set_from_env(&format!("CRISPER_{prefix}_BATCH_SIZE"), &mut server.batch_size);
+            set_from_env(&format!("CRISPER_{prefix}_PARALLEL"), &mut server.parallel);
+            set_from_env(&format!("CRISPER_{prefix}_GPU_LAYERS"), &mut server.gpu_layers);
+        }
+    }
+
+    /// 잘못된 값이 있으면 모든 오류를 한 번에 모아서 반환합니다.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+
+        if self.database.path.trim().is_empty() { errors.push("database.path is empty".to_string()); }
+        if self.servers.host.trim().is_empty() { errors.push("servers.host is empty".to_string()); }
+        if self.llm.chat_model.trim().is_empty() { errors.push("llm.chat_model is empty".to_string()); }
+        if self.llm.entity_categories.iter().all(|c| c.trim().is_empty()) {
+            errors.push("llm.entity_categories is empty".to_string());
+        }
+        if self.llm.retry.max_attempts == 0 { errors.push("llm.retry.max_attempts must be > 0".to_string()); }
+        if self.retrieval.top_k == 0 { errors.push("retrieval.top_k must be > 0".to_string()); }
+        if !(0.0..=1.0).contains(&self.resolution.auto_merge_threshold) {
+            errors.push("resolution.auto_merge_threshold must be between 0 and 1".to_string());
+        }
+        if self.keywords.top_n == 0 { errors.push("keywords.top_n must be > 0".to_string()); }
+        if self.jobs.workers == 0 { errors.push("jobs.workers must be > 0".to_string()); }
+        if self.ingest.max_depth == 0 { errors.push("ingest.max_depth must be > 0".to_string()); }
+        if let Err(e) = validate_patterns(&self.ingest) { errors.push(format!("ingest: {}", e)); }
+        if self.chunking.max_tokens == 0 { errors.push("chunking.max_tokens must be > 0".to_string()); }
+        if self.chunking.overlap_tokens >= self.chunking.max_tokens {
+            errors.push("chunking.overlap_tokens must be < chunking.max_tokens".to_string());
+        }
+
+        let mut ports = Vec::new();
+        for (name, server) in self.servers.all() {
+            if server.port == 0 { errors.push(format!("servers.{name}.port must be > 0")); }
+            if ports.contains(&server.port) { errors.push(format!("servers.{name}.port {} is already used", server.port)); }
+            ports.push(server.port);
+
+            if server.ctx_size < 256 { errors.push(format!("servers.{name}.ctx_size must be >= 256")); }
+            if server.batch_size == 0 { errors.push(format!("servers.{name}.batch_size must be > 0")); }
+            if server.parallel == 0 { errors.push(format!("servers.{name}.parallel must be > 0")); }
+            if server.enabled && server.model_path.trim().is_empty() {
+                errors.push(format!("servers.{name}.model_path is empty"));
+            }
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(format!("Invalid settings: {}", errors.join(", "))) }
+    }
+
+    pub fn embed_url(&self) -> String { self.servers.url(&self.servers.embed) }
+    pub fn chat_url(&self) -> String { self.servers.url(&self.servers.chat) }
+    pub fn rerank_url(&self) -> String { self.servers.url(&self.servers.rerank) }
+}
+
+impl ServersConfig {
+    pub fn all(&self) -> [(&'static str, &ServerConfig); 3] {
+        [("embed", &self.embed), ("chat", &self.chat), ("rerank", &self.rerank)]
+    }
+
+    /// OpenAI 호환 API 주소 (http://host:port/v1)
+    pub fn url(&self, server: &ServerConfig) -> String {
+        format!("http://{}:{}/v1", self.host, server.port)
+    }
+}
+
+/// 환경변수가 바꾼 값(`file` != `with_env`)은 `file`의 값으로 되돌립니다.
+fn keep_file_values(target: &mut JsonValue, file: &JsonValue, with_env: &JsonValue) {
+    match target {
+        JsonValue::Object(fields) => {
+            for (key, value) in fields.iter_mut() {
+                if let (Some(f), Some(e)) = (file.get(key), with_env.get(key)) {
+                    keep_file_values(value, f, e);
+                }
+            }
+        }
+        _ if file != with_env => *target = file.clone(),
+        _ => {}
+    }
+}
+
+fn set_from_env<T: FromStr>(key: &str, target: &mut T) {
+    if let Ok(raw) = env::var(key) {
+        match raw.trim().parse::<T>() {
+            Ok(v) => *target = v,
+            Err(_) => eprintln!("⚠️ Ignoring invalid {}={}", key, raw),
+        }
+    }
+}
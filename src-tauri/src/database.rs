@@ -4,11 +4,41 @@ use surrealdb::engine::local::{Db, RocksDb};
 use surrealdb::Surreal;
 
 // 🚨 pub 추가
-pub async fn init_db() -> surrealdb::Result<Surreal<Db>> {
-    // 경로 수정: 실행 파일 기준 상위 폴더 등 적절히
-    let db = Surreal::new::<RocksDb>("../data/crisper_db").await?;
+/// `path`: 설정의 database.path (상대 경로는 실행 위치 기준)
+pub async fn init_db(path: &str) -> surrealdb::Result<Surreal<Db>> {
+    let db = Surreal::new::<RocksDb>(path).await?;
     
     db.use_ns("crisper_ns").use_db("crisper_db").await?;
+
+    define_search_schema(&db).await?;
     
     Ok(db)
-}
\ No newline at end of file
+}
+
+/// Chunk/Entity 임베딩 필드에 HNSW 벡터 인덱스를 정의합니다. (이미 있으면 무시)
+/// 차원 수는 임베딩 모델에 따라 달라지므로 실제 벡터 길이를 받아서 정의합니다.
+pub async fn ensure_vector_index(db: &Surreal<Db>, table: &str, dimension: usize) -> surrealdb::Result<()> {
+    if dimension == 0 { return Ok(()); }
+
+    let sql = format!(
+        "DEFINE INDEX IF NOT EXISTS {table}_embedding_hnsw ON TABLE {table} FIELDS embedding HNSW DIMENSION {dimension} DIST COSINE"
+    );
+    db.query(sql).await?.check()?;
+
+    Ok(())
+}
+
+/// Chunk 본문에 대한 전문 검색(BM25) Analyzer와 인덱스를 정의합니다.
+/// 제품 코드/약어/한글 고유명사를 잡기 위해 공백·문자종류·구두점 단위로 자르고,
+/// 조사가 붙은 한글 어절("삼성전자는")도 매칭되도록 edgengram을 적용합니다.
+async fn define_search_schema(db: &Surreal<Db>) -> surrealdb::Result<()> {
+    db.query("
+        DEFINE ANALYZER IF NOT EXISTS chunk_analyzer
+            TOKENIZERS blank, class, punct
+            FILTERS lowercase, edgengram(2, 10);
+        DEFINE INDEX IF NOT EXISTS chunk_content_search ON TABLE chunk
+            FIELDS content SEARCH ANALYZER chunk_analyzer BM25 HIGHLIGHTS;
+    ").await?.check()?;
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+// src/ingest/folders.rs
+
+use std::path::Path;
+use surrealdb::engine::local::Db;
+use surrealdb::sql::Thing;
+use surrealdb::Surreal;
+
+use super::source_path_of;
+
+/// 폴더 Record ID (절대 경로를 그대로 ID로 사용해 같은 폴더는 항상 같은 노드)
+fn folder_id(path: &str) -> Thing {
+    Thing::from(("folder", path))
+}
+
+/// 문서의 상대 경로를 따라 Folder 노드를 만들고 연결합니다.
+/// 예: root/a/b/c.md -> folder(root) -> contains -> folder(a) -> contains -> folder(a/b) -> contains -> document
+pub async fn link_folders(db: &Surreal<Db>, root: &Path, relative_path: &str, doc: &Thing) -> surrealdb::Result<()> {
+    let root_path = source_path_of(root);
+    let root_name = root.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| root_path.clone());
+
+    let mut parent = folder_id(&root_path);
+    upsert_folder(db, &parent, &root_name, &root_path, "").await?;
+
+    // 마지막 요소(파일 이름)를 뺀 폴더들
+    let dirs: Vec<&str> = relative_path.split('/').collect();
+    let mut rel = String::new();
+    for name in &dirs[..dirs.len().saturating_sub(1)] {
+        if !rel.is_empty() { rel.push('/'); }
+        rel.push_str(name);
+
+        let path = source_path_of(&root.join(&rel));
+        let folder = folder_id(&path);
+        upsert_folder(db, &folder, name, &path, &rel).await?;
+        relate_once(db, &parent, &folder).await?;
+        parent = folder;
+    }
+
+    relate_once(db, &parent, doc).await
+}
+
+async fn upsert_folder(db: &Surreal<Db>, id: &Thing, name: &str, path: &str, relative_path: &str) -> surrealdb::Result<()> {
+    db.query("UPSERT $id SET name = $name, path = $path, relative_path = $rel, created_at = created_at ?? time::now()")
+        .bind(("id", id.clone()))
+        .bind(("name", name.to_string()))
+        .bind(("path", path.to_string()))
+        .bind(("rel", relative_path.to_string()))
+        .await?.check()?;
+    Ok(())
+}
+
+/// parent -> contains -> child (이미 연결되어 있으면 그대로 둠)
+async fn relate_once(db: &Surreal<Db>, parent: &Thing, child: &Thing) -> surrealdb::Result<()> {
+    let existing: Vec<Thing> = db
+        .query("SELECT VALUE id FROM contains WHERE in = $p AND out = $c")
+        .bind(("p", parent.clone()))
+        .bind(("c", child.clone()))
+        .await?.take(0)?;
+    if existing.is_empty() {
+        db.query("RELATE $p->contains->$c")
+            .bind(("p", parent.clone()))
+            .bind(("c", child.clone()))
+            .await?.check()?;
+    }
+    Ok(())
+}
+
+/// 하위 폴더/문서가 하나도 없는 Folder 노드를 지웁니다. (빈 폴더가 없어질 때까지 위로 올라가며 반복)
+pub async fn prune_empty_folders(db: &Surreal<Db>) -> surrealdb::Result<()> {
+    loop {
+        let empty: Vec<Thing> = db
+            .query("SELECT VALUE id FROM folder WHERE count(->contains) = 0")
+            .await?.take(0)?;
+        if empty.is_empty() { return Ok(()); }
+
+        db.query("
+            DELETE contains WHERE out INSIDE $folders;
+            DELETE folder WHERE id INSIDE $folders;
+        ")
+            .bind(("folders", empty))
+            .await?.check()?;
+    }
+}
@@ -0,0 +1,458 @@
+// src/ingest/mod.rs
+
+pub mod folders;
+pub mod scan;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use surrealdb::engine::local::Db;
+use surrealdb::sql::Thing;
+use surrealdb::Surreal;
+use uuid::Uuid;
+
+use crate::chunker::{chunk_pages, ChunkerOptions};
+use crate::llm::embedder::embed_texts;
+use crate::llm::extractor::analyze_content;
+use crate::models::{ChunkNode, ChunkStatus, DocumentNode};
+use crate::loaders::loader_for;
+use crate::AppState;
+
+/// 파일 하나를 처리한 결과
+#[derive(Debug, Clone, PartialEq)]
+pub enum IngestOutcome {
+    /// 처음 보는 파일
+    Added,
+    /// 같은 경로의 파일 내용이 바뀌어 다시 처리함 (이전 Chunk는 교체)
+    Updated,
+    /// 처리하지 않음 (내용이 같은 파일이 이미 있음, 빈 문서 등)
+    Skipped(String),
+    /// 같은 경로의 이전 문서를 지움 (바뀐 내용이 다른 문서와 같아 새로 만들지 않음)
+    Removed(String),
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FailedFile {
+    pub path: String,
+    pub error: String,
+}
+
+/// 폴더 Ingest 결과 (파일 경로별)
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct IngestReport {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub skipped: Vec<SkippedFile>,
+    pub failed: Vec<FailedFile>,
+    /// 지운 문서 (원본 파일이 사라졌거나, 바뀐 내용이 다른 문서와 같음)
+    pub removed: Vec<String>,
+}
+
+impl IngestReport {
+    pub fn record(&mut self, path: &Path, result: Result<IngestOutcome, String>) {
+        let path = path.display().to_string();
+        match result {
+            Ok(IngestOutcome::Added) => self.added.push(path),
+            Ok(IngestOutcome::Updated) => self.updated.push(path),
+            Ok(IngestOutcome::Skipped(reason)) => self.skipped.push(SkippedFile { path, reason }),
+            Ok(IngestOutcome::Removed(_)) => self.removed.push(path),
+            Err(error) => self.failed.push(FailedFile { path, error }),
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "added {}, updated {}, skipped {}, failed {}, removed {}",
+            self.added.len(), self.updated.len(), self.skipped.len(), self.failed.len(), self.removed.len()
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ExistingDocument {
+    id: Thing,
+    #[serde(default)]
+    content_hash: Option<String>,
+    #[serde(default)]
+    source_path: Option<String>,
+    #[serde(default)]
+    relative_path: Option<String>,
+}
+
+/// 파일 내용의 SHA-256 (소문자 hex)
+pub fn file_hash(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 { break; }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// DB에 저장하는 파일 경로 (가능하면 절대 경로)
+pub fn source_path_of(path: &Path) -> String {
+    std::fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
+
+/// 파일 하나를 (형식에 맞는 로더로 읽어) Document + Chunk로 저장합니다.
+/// 같은 경로의 문서 내용이 바뀌었으면 기존 Chunk를 지우고 다시 만들고, 내용 해시가 같은 다른 문서가 있으면 건너뜁니다.
+/// (바뀐 내용이 다른 문서와 같으면 이 경로의 이전 문서만 지움)
+/// 해시는 Chunk까지 모두 저장한 뒤 기록하므로, 중간에 끊긴 문서는 다음 실행에서 처음부터 다시 처리됩니다.
+/// 내용이 같은 문서의 원본 파일이 없어졌다면 이동/이름 변경으로 보고 경로만 바꿉니다.
+/// `root`: Ingest 폴더. 있으면 상대 경로를 저장하고 Folder 노드(folder -> contains -> document)로 연결
+/// `session`: 이 파일을 가져온 작업(Event). 있으면 event -> imported -> document로 연결
+pub async fn ingest_file(
+    state: &AppState,
+    file_path: &Path,
+    root: Option<&Path>,
+    session: Option<&Thing>,
+    chunking: &ChunkerOptions,
+) -> Result<IngestOutcome, String> {
+    let db = &state.db;
+    let gen = state.chat_endpoint(); // 로컬 LLM 서버 주소 (설정의 servers.chat)
+    let retry = state.settings().llm.retry;
+    let categories = state.settings().llm.entity_categories;
+
+    let original_filename = file_path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| file_path.display().to_string());
+    let source_path = source_path_of(file_path);
+    let relative_path = root.and_then(|r| scan::relative_path_of(r, file_path));
+
+    // 0. 내용 해시로 중복/변경 확인
+    let content_hash = file_hash(file_path).map_err(|e| e.to_string())?;
+    let existing: Vec<ExistingDocument> = db
+        .query("SELECT id, content_hash, source_path, relative_path FROM document WHERE source_path = $path OR content_hash = $hash")
+        .bind(("path", source_path.clone()))
+        .bind(("hash", content_hash.clone()))
+        .await.map_err(|e| e.to_string())?
+        .take(0).map_err(|e| e.to_string())?;
+
+    let (previous, others): (Vec<ExistingDocument>, Vec<ExistingDocument>) = existing.into_iter()
+        .partition(|d| d.source_path.as_deref() == Some(source_path.as_str()));
+    let previous = previous.into_iter().next();
+
+    if let Some(prev) = previous.as_ref().filter(|d| d.content_hash.as_deref() == Some(content_hash.as_str())) {
+        // 폴더 구조가 없던 이전 문서는 상대 경로와 Folder 연결만 채움
+        if prev.relative_path.is_none() {
+            if let (Some(root), Some(rel)) = (root, &relative_path) {
+                link_document(db, root, rel, &prev.id).await.map_err(|e| e.to_string())?;
+            }
+        }
+        println!("    ⏭️ Skipped (unchanged)");
+        return Ok(IngestOutcome::Skipped("unchanged".to_string()));
+    }
+
+    if let Some(same) = others.iter().find(|d| d.content_hash.as_deref() == Some(content_hash.as_str())) {
+        let reason = format!("duplicate of {}", same.source_path.as_deref().unwrap_or("?"));
+        // 이 경로의 이전 내용은 더 이상 없으므로 먼저 지움
+        if let Some(prev) = &previous {
+            remove_document(db, &prev.id).await.map_err(|e| e.to_string())?;
+            folders::prune_empty_folders(db).await.map_err(|e| e.to_string())?;
+        }
+
+        // 원래 파일이 없어졌다면 이동/이름 변경: 다시 분석하지 않고 경로만 옮김
+        if let Some(old) = same.source_path.as_deref().filter(|p| !Path::new(p).exists()) {
+            println!("    🚚 Moved from {}", old);
+            move_document(db, &same.id, &original_filename, &source_path, root, relative_path.as_deref())
+                .await.map_err(|e| e.to_string())?;
+            return Ok(IngestOutcome::Updated);
+        }
+
+        if previous.is_some() {
+            println!("    🗑️ Removed old version ({})", reason);
+            return Ok(IngestOutcome::Removed(reason));
+        }
+        println!("    ⏭️ Skipped ({})", reason);
+        return Ok(IngestOutcome::Skipped(reason));
+    }
+    let previous = previous.map(|d| d.id);
+
+    // A. 형식별 로더로 섹션(페이지/제목 단위) 추출
+    let loader = loader_for(file_path).ok_or_else(|| format!("Unsupported file type: {}", file_path.display()))?;
+    print!("    📖 Extracting sections ({:?})... ", loader.format());
+    let sections = match loader.load(file_path) {
+        Ok(s) => {
+            println!("Done ({} sections)", s.len());
+            s
+        },
+        Err(e) => {
+            println!("❌ Failed: {}", e);
+            return Err(e.to_string());
+        }
+    };
+
+    if sections.is_empty() {
+        println!("    ⚠️ Skipped (Empty document)");
+        return Ok(IngestOutcome::Skipped("empty document".to_string()));
+    }
+    let pages: Vec<String> = sections.iter().map(|s| s.text.clone()).collect();
+
+    // B. Document(부모) 요약 생성 (앞 2페이지만 사용)
+    let summary_context = pages.iter().take(2).cloned().collect::<Vec<String>>().join("\n");
+
+    println!("    🤖 Summarizing Document (Parent)...");
+    let mut doc_meta = HashMap::new();
+    doc_meta.insert("format".to_string(), json!(loader.format()));
+    // 실패 시 가짜 분석 결과를 저장하지 않고 원인만 기록
+    match analyze_content(&gen, &retry, &categories, &summary_context).await {
+        Ok(parent_analysis) => { doc_meta.insert("analysis".to_string(), json!(parent_analysis)); }
+        Err(e) => {
+            println!("    ⚠️ Document analysis failed: {}", e);
+            doc_meta.insert("analysis_error".to_string(), json!(e));
+        }
+    }
+
+    let document = DocumentNode {
+        id: None,
+        filename: original_filename.clone(),
+        created_at: Utc::now(),
+        content_hash: None, // 모든 Chunk를 저장한 뒤 기록
+        source_path: Some(source_path),
+        relative_path: relative_path.clone(),
+        metadata: doc_meta,
+    };
+
+    // Document 저장: 내용이 바뀐 파일은 같은 ID를 유지하고 이전 Chunk만 교체
+    let (doc_id, outcome) = match previous {
+        Some(id) => {
+            let removed = remove_document_chunks(db, &id).await.map_err(|e| e.to_string())?;
+            println!("    ♻️ Content changed, replaced {} old chunks", removed);
+            let doc_id = id.id.to_raw();
+            let _: Option<DocumentNode> = db.update(("document", doc_id.as_str()))
+                .content(document)
+                .await.map_err(|e| e.to_string())?;
+            (doc_id, IngestOutcome::Updated)
+        }
+        None => {
+            let doc_id = Uuid::new_v4().to_string();
+            let _: Option<DocumentNode> = db.create(("document", doc_id.as_str()))
+                .content(document)
+                .await.map_err(|e| e.to_string())?;
+            (doc_id, IngestOutcome::Added)
+        }
+    };
+
+    // Event -> Document 연결 (문자열이 아닌 Record ID로 바인딩해야 RELATE가 동작)
+    let doc_thing = Thing::from(("document", doc_id.as_str()));
+    if let Some(session) = session {
+        let _ = db.query("RELATE $e->imported->$d")
+            .bind(("e", session.clone()))
+            .bind(("d", doc_thing.clone()))
+            .await.ok();
+    }
+    if let (Some(root), Some(rel)) = (root, &relative_path) {
+        if let Err(e) = folders::link_folders(db, root, rel, &doc_thing).await {
+            println!("    ⚠️ Folder link failed: {}", e);
+        }
+    }
+
+    // C. 청크 나누기 (페이지 범위와 글자 오프셋을 함께 보관해 인용 위치를 찾을 수 있게 함)
+    let text_chunks = chunk_pages(&pages, chunking);
+    let chunks: Vec<String> = text_chunks.iter().map(|c| c.content.clone()).collect();
+    println!("    ✂️ {} sections -> {} chunks", pages.len(), chunks.len());
+
+    // 청크 임베딩 생성 (배치). 실패해도 저장은 계속하고 backfill_embeddings로 보충
+    print!("    🧬 Embedding {} chunks... ", chunks.len());
+    let mut embeddings = match embed_texts(&state.embed_client(), &chunks).await {
+        Ok(v) => {
+            println!("Done");
+            v
+        },
+        Err(e) => {
+            println!("❌ Failed: {}", e);
+            vec![]
+        }
+    };
+    embeddings.resize(chunks.len(), vec![]);
+    if let Some(dim) = embeddings.iter().map(|v| v.len()).find(|&n| n > 0) {
+        if let Err(e) = crate::database::ensure_vector_index(db, "chunk", dim).await {
+            println!("    ⚠️ Vector index: {}", e);
+        }
+    }
+
+    for (i, chunk) in text_chunks.iter().enumerate() {
+        let chunk_uuid = Uuid::new_v4().to_string();
+        let txt = &chunk.content;
+
+        // 청커의 페이지 번호(섹션 순서) -> 로더가 준 위치 (PDF는 실제 페이지 번호)
+        let first = &sections[chunk.page_start - 1];
+        let (page_start, page_end) = (first.index, sections[chunk.page_end - 1].index);
+
+        print!("      Running LLM Analysis on Chunk #{} (p.{}-{}, Len: {})... ", i + 1, page_start, page_end, txt.len());
+
+        // Chunk 메타데이터 구성
+        let mut chunk_meta = HashMap::new();
+
+        // 청크별 분석 실행 (실패하면 analysis 대신 원인을 analysis_error에 기록하고 failed로 표시)
+        let status = match analyze_content(&gen, &retry, &categories, txt).await {
+            Ok(res) => {
+                println!("✅ Done");
+                // Step 2(Graph)를 위해 분석 데이터를 통째로 저장
+                chunk_meta.insert("analysis".to_string(), json!(res));
+                ChunkStatus::Analyzed
+            },
+            Err(e) => {
+                println!("\n      ❌ ERROR: {}", e);
+                chunk_meta.insert("analysis_error".to_string(), json!(e));
+                ChunkStatus::Failed
+            }
+        };
+
+        // page_number는 청크가 시작하는 페이지/섹션 (검색 결과/인용 표시용)
+        chunk_meta.insert("page_number".to_string(), json!(page_start));
+        chunk_meta.insert("page_start".to_string(), json!(page_start));
+        chunk_meta.insert("page_end".to_string(), json!(page_end));
+        chunk_meta.insert("char_start".to_string(), json!(chunk.char_start));
+        chunk_meta.insert("char_end".to_string(), json!(chunk.char_end));
+        chunk_meta.insert("chunk_index".to_string(), json!(i));
+        chunk_meta.insert("chunk_strategy".to_string(), json!(chunking.strategy));
+        if let Some(heading) = &chunk.heading {
+            chunk_meta.insert("heading".to_string(), json!(heading));
+        }
+        if let Some(title) = &first.title {
+            chunk_meta.insert("section_title".to_string(), json!(title));
+        }
+
+        // Chunk 저장
+        let _chunk: Option<ChunkNode> = db.create(("chunk", chunk_uuid.as_str()))
+            .content(ChunkNode {
+                id: None,
+                content: txt.clone(),
+                page_index: page_start - 1,
+                embedding: std::mem::take(&mut embeddings[i]),
+                status,
+                metadata: chunk_meta
+            }).await.map_err(|e| e.to_string())?;
+
+        // Document -> Chunk 연결
+        let _ = db.query("RELATE $d->contains->$c")
+            .bind(("d", doc_thing.clone()))
+            .bind(("c", Thing::from(("chunk", chunk_uuid.as_str()))))
+            .await.ok();
+    }
+
+    db.query("UPDATE $doc SET content_hash = $hash")
+        .bind(("doc", doc_thing))
+        .bind(("hash", content_hash))
+        .await.map_err(|e| e.to_string())?
+        .check().map_err(|e| e.to_string())?;
+
+    Ok(outcome)
+}
+
+/// 이미 저장된 문서에 상대 경로를 기록하고 Folder 노드에 연결합니다.
+async fn link_document(db: &Surreal<Db>, root: &Path, relative_path: &str, doc: &Thing) -> surrealdb::Result<()> {
+    db.query("UPDATE $doc SET relative_path = $rel")
+        .bind(("doc", doc.clone()))
+        .bind(("rel", relative_path.to_string()))
+        .await?.check()?;
+    folders::link_folders(db, root, relative_path, doc).await
+}
+
+/// 파일이 옮겨진 문서의 경로/이름을 바꾸고 Folder 연결을 다시 만듭니다.
+async fn move_document(
+    db: &Surreal<Db>,
+    doc: &Thing,
+    filename: &str,
+    source_path: &str,
+    root: Option<&Path>,
+    relative_path: Option<&str>,
+) -> surrealdb::Result<()> {
+    db.query("
+        UPDATE $doc SET filename = $filename, source_path = $path, relative_path = $rel;
+        DELETE contains WHERE out = $doc;
+    ")
+        .bind(("doc", doc.clone()))
+        .bind(("filename", filename.to_string()))
+        .bind(("path", source_path.to_string()))
+        .bind(("rel", relative_path.map(str::to_string)))
+        .await?.check()?;
+
+    if let (Some(root), Some(rel)) = (root, relative_path) {
+        folders::link_folders(db, root, rel, doc).await?;
+    }
+    folders::prune_empty_folders(db).await
+}
+
+/// 문서와 그 Chunk, 연결된 Edge를 모두 지웁니다.
+pub async fn remove_document(db: &Surreal<Db>, doc: &Thing) -> surrealdb::Result<()> {
+    remove_document_chunks(db, doc).await?;
+    db.query("
+        DELETE contains WHERE out = $doc;
+        DELETE imported WHERE out = $doc;
+        DELETE $doc;
+    ")
+        .bind(("doc", doc.clone()))
+        .await?.check()?;
+    Ok(())
+}
+
+/// `path`(파일 또는 폴더)에서 온 문서
+async fn documents_under(db: &Surreal<Db>, path: &Path) -> surrealdb::Result<Vec<ExistingDocument>> {
+    let path = source_path_of(path);
+    let prefix = format!("{}{}", path, std::path::MAIN_SEPARATOR);
+    db.query("SELECT id, content_hash, source_path, relative_path FROM document WHERE source_path = $path OR string::starts_with(source_path, $prefix)")
+        .bind(("path", path))
+        .bind(("prefix", prefix))
+        .await?.take(0)
+}
+
+/// `path`(파일 또는 폴더)에서 온 문서가 있는지 (폴더 감시에서 지울 것이 있을 때만 작업을 만들기 위함)
+pub async fn has_documents_under(db: &Surreal<Db>, path: &Path) -> surrealdb::Result<bool> {
+    Ok(!documents_under(db, path).await?.is_empty())
+}
+
+/// `path`(파일 또는 폴더)에서 온 문서를 모두 지웁니다. 반환: 지운 문서의 원본 경로
+pub async fn remove_documents_under(db: &Surreal<Db>, path: &Path) -> surrealdb::Result<Vec<String>> {
+    let docs = documents_under(db, path).await?;
+
+    let mut removed = Vec::new();
+    for doc in docs {
+        remove_document(db, &doc.id).await?;
+        removed.push(doc.source_path.unwrap_or_default());
+    }
+    if !removed.is_empty() {
+        folders::prune_empty_folders(db).await?;
+    }
+    Ok(removed)
+}
+
+/// Document에 속한 Chunk와 그 Chunk에 연결된 Edge(contains, mentions, 근거로 쓰인 related_to, 답변의 cited)를 지웁니다.
+/// 반환: 지운 Chunk 수
+pub async fn remove_document_chunks(db: &Surreal<Db>, doc: &Thing) -> surrealdb::Result<usize> {
+    let chunks: Vec<Thing> = db
+        .query("SELECT VALUE out FROM contains WHERE in = $doc")
+        .bind(("doc", doc.clone()))
+        .await?.take(0)?;
+
+    db.query("
+        DELETE mentions WHERE in INSIDE $chunks;
+        DELETE cited WHERE out INSIDE $chunks;
+        DELETE related_to WHERE evidence INSIDE $chunks;
+        DELETE contains WHERE in = $doc;
+        DELETE chunk WHERE id INSIDE $chunks;
+    ")
+        .bind(("doc", doc.clone()))
+        .bind(("chunks", chunks.clone()))
+        .await?.check()?;
+
+    Ok(chunks.len())
+}
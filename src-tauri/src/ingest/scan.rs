@@ -0,0 +1,170 @@
+// src/ingest/scan.rs
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::SkippedFile;
+use crate::loaders::loader_for;
+
+/// 폴더 Ingest 시 파일을 고르는 규칙
+/// 패턴은 Ingest 폴더 기준 상대 경로("/" 구분)에 대해 검사합니다. (예: "docs/**/*.md", "**/draft/**")
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ScanOptions {
+    /// 하위 폴더까지 읽을지 (false면 선택한 폴더의 파일만)
+    pub recursive: bool,
+    /// 포함할 파일 패턴 (비어 있으면 지원하는 형식의 모든 파일)
+    pub include: Vec<String>,
+    /// 제외할 파일/폴더 패턴 (폴더가 걸리면 그 아래는 읽지 않음)
+    pub exclude: Vec<String>,
+    /// 최대 깊이 (1 = 선택한 폴더 바로 아래 파일만)
+    pub max_depth: usize,
+    /// 심볼릭 링크를 따라갈지 (순환 링크는 건너뜀)
+    pub follow_symlinks: bool,
+    /// 이보다 큰 파일은 건너뜀 (MB, 0이면 제한 없음)
+    pub max_file_size_mb: u64,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            recursive: true,
+            include: vec![],
+            // 숨김 파일/폴더(.git 등)와 흔한 빌드/의존성 폴더
+            exclude: ["**/.*", "**/node_modules", "**/target", "**/__pycache__", "**/~$*"]
+                .iter().map(|s| s.to_string()).collect(),
+            max_depth: 32,
+            follow_symlinks: false,
+            max_file_size_mb: 100,
+        }
+    }
+}
+
+/// Ingest 대상 파일
+#[derive(Debug, Clone)]
+pub struct ScannedFile {
+    pub path: PathBuf,
+    /// Ingest 폴더 기준 상대 경로 ("/" 구분)
+    pub relative_path: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ScanResult {
+    pub files: Vec<ScannedFile>,
+    /// 크기 제한, 읽기 오류, 순환 링크 등으로 건너뛴 파일
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// 패턴 목록을 GlobSet으로 컴파일합니다.
+fn build_set(patterns: &[String]) -> anyhow::Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for p in patterns {
+        builder.add(Glob::new(p).map_err(|e| anyhow::anyhow!("Invalid glob '{}': {}", p, e))?);
+    }
+    Ok(builder.build()?)
+}
+
+/// 설정 검증용: 패턴이 모두 올바른지 확인
+pub fn validate_patterns(opts: &ScanOptions) -> anyhow::Result<()> {
+    build_set(&opts.include)?;
+    build_set(&opts.exclude)?;
+    Ok(())
+}
+
+/// `root` 기준 상대 경로 ("/" 구분, OS와 무관하게 같은 형태로 저장)
+pub fn relative_path_of(root: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(root).ok()?;
+    let parts: Vec<String> = rel.components().map(|c| c.as_os_str().to_string_lossy().to_string()).collect();
+    if parts.is_empty() { None } else { Some(parts.join("/")) }
+}
+
+/// 파일 하나가 `root` 기준으로 Ingest 대상인지 확인합니다. (scan_directory와 같은 규칙, 폴더 감시용)
+/// 대상이면 상대 경로를 돌려줌
+pub fn accepts_file(root: &Path, path: &Path, opts: &ScanOptions) -> anyhow::Result<Option<String>> {
+    let Some(rel) = relative_path_of(root, path) else { return Ok(None) };
+    if loader_for(path).is_none() { return Ok(None); }
+
+    // 링크를 따라가지 않으면 scan_directory처럼 링크 파일과 링크된 폴더 아래의 파일도 제외
+    let metadata = if opts.follow_symlinks { std::fs::metadata(path) } else { std::fs::symlink_metadata(path) };
+    let Ok(metadata) = metadata else { return Ok(None) };
+    if !metadata.is_file() { return Ok(None); }
+
+    let parts: Vec<&str> = rel.split('/').collect();
+    let max_depth = if opts.recursive { opts.max_depth.max(1) } else { 1 };
+    if parts.len() > max_depth { return Ok(None); }
+
+    if !opts.follow_symlinks {
+        let linked_dir = (1..parts.len()).any(|n| {
+            std::fs::symlink_metadata(root.join(parts[..n].join("/"))).is_ok_and(|m| m.file_type().is_symlink())
+        });
+        if linked_dir { return Ok(None); }
+    }
+
+    // 상위 폴더 중 하나라도 제외 패턴에 걸리면 제외
+    let exclude = build_set(&opts.exclude)?;
+    let excluded = (1..=parts.len()).any(|n| exclude.is_match(parts[..n].join("/")));
+    if excluded { return Ok(None); }
+    if !opts.include.is_empty() && !build_set(&opts.include)?.is_match(&rel) { return Ok(None); }
+
+    let max_bytes = opts.max_file_size_mb.saturating_mul(1024 * 1024);
+    if max_bytes > 0 && metadata.len() > max_bytes {
+        anyhow::bail!("larger than {} MB", opts.max_file_size_mb);
+    }
+
+    Ok(Some(rel))
+}
+
+/// `root` 아래에서 Ingest할 파일을 찾습니다. (상대 경로 순으로 정렬)
+pub fn scan_directory(root: &Path, opts: &ScanOptions) -> anyhow::Result<ScanResult> {
+    if !root.is_dir() {
+        anyhow::bail!("Not a directory: {}", root.display());
+    }
+    let include = build_set(&opts.include)?;
+    let exclude = build_set(&opts.exclude)?;
+    let max_depth = if opts.recursive { opts.max_depth.max(1) } else { 1 };
+    let max_bytes = opts.max_file_size_mb.saturating_mul(1024 * 1024);
+
+    let walker = WalkDir::new(root)
+        .follow_links(opts.follow_symlinks)
+        .max_depth(max_depth)
+        .sort_by_file_name()
+        .into_iter()
+        // 제외 패턴에 걸린 폴더는 하위까지 통째로 건너뜀 (루트 자체는 검사하지 않음)
+        .filter_entry(|e| e.depth() == 0 || !relative_path_of(root, e.path()).is_some_and(|rel| exclude.is_match(&rel)));
+
+    let mut result = ScanResult::default();
+    for entry in walker {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                // 권한 없음, 순환 심볼릭 링크 등
+                let path = e.path().map(|p| p.display().to_string()).unwrap_or_default();
+                result.skipped.push(SkippedFile { path, reason: e.to_string() });
+                continue;
+            }
+        };
+        if !entry.file_type().is_file() { continue; }
+
+        let path = entry.path();
+        let Some(rel) = relative_path_of(root, path) else { continue };
+        if loader_for(path).is_none() { continue; }
+        if !opts.include.is_empty() && !include.is_match(&rel) { continue; }
+
+        if max_bytes > 0 {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            if size > max_bytes {
+                result.skipped.push(SkippedFile {
+                    path: path.display().to_string(),
+                    reason: format!("larger than {} MB", opts.max_file_size_mb),
+                });
+                continue;
+            }
+        }
+
+        result.files.push(ScannedFile { path: path.to_path_buf(), relative_path: rel });
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,330 @@
+// src/jobs/mod.rs
+
+pub mod runner;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use surrealdb::engine::local::Db;
+use surrealdb::sql::Thing;
+use surrealdb::Surreal;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+use crate::chunker::ChunkerOptions;
+use crate::ingest::scan::ScanOptions;
+use crate::keywords::KeywordMode;
+use crate::AppState;
+
+/// 작업 상태가 바뀔 때마다 프론트엔드로 보내는 이벤트 이름 (payload: Job)
+pub const JOB_EVENT: &str = "job-update";
+
+/// 작업 실행 옵션
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct JobOptions {
+    /// 동시에 실행할 작업 수 (같은 그룹의 작업은 하나씩만 실행)
+    pub workers: usize,
+}
+
+impl Default for JobOptions {
+    fn default() -> Self {
+        Self { workers: 2 }
+    }
+}
+
+/// 작업 종류와 인자. DB에 그대로 저장되므로 앱을 다시 켜도 같은 작업을 이어서 실행할 수 있음
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum JobRequest {
+    /// 폴더 Ingest (ingest_documents)
+    Ingest {
+        path: String,
+        #[serde(default)]
+        chunking: Option<ChunkerOptions>,
+        #[serde(default)]
+        scan: Option<ScanOptions>,
+    },
+    /// Chunk -> Entity Graph 연결 (construct_graph)
+    Graph {
+        #[serde(default)]
+        mode: Option<KeywordMode>,
+    },
+    /// 벡터가 없는 Chunk/Entity 임베딩 보충 (backfill_embeddings)
+    Embed,
+    /// 감시 폴더 변경 반영 (paths가 비어 있으면 폴더 전체 동기화)
+    Watch {
+        root: String,
+        #[serde(default)]
+        paths: Vec<String>,
+    },
+}
+
+impl JobRequest {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JobRequest::Ingest { .. } => "ingest",
+            JobRequest::Graph { .. } => "graph",
+            JobRequest::Embed => "embed",
+            JobRequest::Watch { .. } => "watch",
+        }
+    }
+
+    /// 같은 그룹의 작업은 한 번에 하나만 실행
+    /// (중복 확인과 저장 사이에 LLM 호출이 있어, 함께 돌면 같은 파일/Chunk를 두 번 처리할 수 있음)
+    fn group(&self) -> &'static str {
+        match self {
+            JobRequest::Ingest { .. } | JobRequest::Watch { .. } => "ingest",
+            other => other.kind(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Paused,
+    Failed,
+    Done,
+    Cancelled,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct JobProgress {
+    pub done: usize,
+    pub total: usize,
+    pub message: String,
+}
+
+/// job 테이블 레코드 (ID는 UUID 문자열)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Job {
+    pub id: String,
+    pub request: JobRequest,
+    pub status: JobStatus,
+    #[serde(default)]
+    pub progress: JobProgress,
+    /// 완료 시 결과 (Ingest: IngestReport, 그 외: 요약 문자열)
+    #[serde(default)]
+    pub result: Option<JsonValue>,
+    #[serde(default)]
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+const RUN: u8 = 0;
+const PAUSE: u8 = 1;
+const CANCEL: u8 = 2;
+
+/// 실행 중인 작업에 보내는 중지 요청 (작업은 항목 사이마다 확인)
+#[derive(Clone, Default)]
+pub struct JobControl(Arc<AtomicU8>);
+
+impl JobControl {
+    fn request(&self, signal: u8) {
+        self.0.store(signal, Ordering::SeqCst);
+    }
+
+    fn requested(&self) -> u8 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+struct RunningJob {
+    group: &'static str,
+    control: JobControl,
+}
+
+/// AppState에 들어가는 작업 관리자 (실제 실행은 runner의 작업자들이 담당)
+#[derive(Default)]
+pub struct JobManager {
+    /// 새 작업이 들어오거나 재개되면 쉬고 있는 작업자 하나를 깨움
+    wake: Notify,
+    /// 두 작업자가 같은 작업을 가져가지 않도록 대기열 조회~상태 변경을 직렬화
+    claim_lock: tokio::sync::Mutex<()>,
+    running: Mutex<HashMap<String, RunningJob>>,
+}
+
+impl JobManager {
+    /// 실행 가능한 가장 오래된 작업을 running으로 바꿔 가져옵니다.
+    async fn claim(&self, db: &Surreal<Db>) -> surrealdb::Result<Option<(Job, JobControl)>> {
+        let _guard = self.claim_lock.lock().await;
+
+        let queued: Vec<Job> = db
+            .query("SELECT *, meta::id(id) AS id FROM job WHERE status = 'queued' ORDER BY created_at ASC")
+            .await?.take(0)?;
+
+        let job = {
+            let running = self.running.lock().unwrap();
+            queued.into_iter().find(|job| !running.values().any(|r| r.group == job.request.group()))
+        };
+        let Some(mut job) = job else { return Ok(None) };
+
+        db.query("UPDATE $id SET status = 'running', started_at = time::now(), finished_at = NONE, error = NONE")
+            .bind(("id", job_thing(&job.id)))
+            .await?.check()?;
+        job.status = JobStatus::Running;
+
+        let control = JobControl::default();
+        self.running.lock().unwrap().insert(job.id.clone(), RunningJob {
+            group: job.request.group(),
+            control: control.clone(),
+        });
+        Ok(Some((job, control)))
+    }
+
+    fn release(&self, id: &str) {
+        self.running.lock().unwrap().remove(id);
+    }
+
+    /// 실행 중이면 중지 요청을 보내고 true
+    fn signal(&self, id: &str, signal: u8) -> bool {
+        match self.running.lock().unwrap().get(id) {
+            Some(job) => {
+                job.control.request(signal);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// 작업 함수가 진행 상황을 알리고 중지 요청을 확인하는 핸들
+#[derive(Clone)]
+pub struct JobContext {
+    pub id: String,
+    pub app: AppHandle,
+    control: JobControl,
+}
+
+impl JobContext {
+    /// 일시정지/취소 요청이 있으면 Err. 작업 함수는 항목(파일, Chunk, 배치) 사이마다 호출해 `?`로 빠져나감
+    pub fn checkpoint(&self) -> Result<(), String> {
+        match self.control.requested() {
+            RUN => Ok(()),
+            PAUSE => Err("Job paused".to_string()),
+            _ => Err("Job cancelled".to_string()),
+        }
+    }
+
+    /// 진행 상황을 저장하고 프론트엔드에 알림
+    pub async fn progress(&self, done: usize, total: usize, message: impl Into<String>) {
+        let state = self.app.state::<AppState>();
+        let progress = JobProgress { done, total, message: message.into() };
+        let updated = state.db.query("UPDATE $id SET progress = $progress")
+            .bind(("id", job_thing(&self.id)))
+            .bind(("progress", progress))
+            .await;
+        if updated.is_ok() {
+            emit_job(&self.app, &self.id).await;
+        }
+    }
+}
+
+fn job_thing(id: &str) -> Thing {
+    Thing::from(("job", id))
+}
+
+pub async fn get_job(db: &Surreal<Db>, id: &str) -> surrealdb::Result<Option<Job>> {
+    db.query("SELECT *, meta::id(id) AS id FROM ONLY $id")
+        .bind(("id", job_thing(id)))
+        .await?.take(0)
+}
+
+pub async fn list_jobs(db: &Surreal<Db>, status: Option<JobStatus>, limit: usize) -> surrealdb::Result<Vec<Job>> {
+    let sql = if status.is_some() {
+        "SELECT *, meta::id(id) AS id FROM job WHERE status = $status ORDER BY created_at DESC LIMIT $limit"
+    } else {
+        "SELECT *, meta::id(id) AS id FROM job ORDER BY created_at DESC LIMIT $limit"
+    };
+    db.query(sql)
+        .bind(("status", status))
+        .bind(("limit", limit))
+        .await?.take(0)
+}
+
+async fn emit_job(app: &AppHandle, id: &str) {
+    if let Ok(Some(job)) = get_job(&app.state::<AppState>().db, id).await {
+        let _ = app.emit(JOB_EVENT, job);
+    }
+}
+
+/// 작업을 대기열에 넣고 쉬고 있는 작업자를 깨웁니다.
+pub async fn submit(app: &AppHandle, request: JobRequest) -> Result<Job, String> {
+    let state = app.state::<AppState>();
+    let id = Uuid::new_v4().to_string();
+    println!("🧵 [Job] Queued {} ({})", request.kind(), id);
+
+    state.db.query("CREATE $id SET request = $request, status = 'queued', progress = { done: 0, total: 0, message: '' }, created_at = time::now()")
+        .bind(("id", job_thing(&id)))
+        .bind(("request", request))
+        .await.map_err(|e| e.to_string())?
+        .check().map_err(|e| e.to_string())?;
+
+    state.jobs.wake.notify_one();
+    emit_job(app, &id).await;
+    get_job(&state.db, &id).await.map_err(|e| e.to_string())?.ok_or("Job create failed".to_string())
+}
+
+/// 취소: 실행 중이면 다음 항목 전에 멈추고, 대기/일시정지 중이면 바로 취소
+pub async fn cancel(app: &AppHandle, id: &str) -> Result<Job, String> {
+    change_state(app, id, CANCEL, &[JobStatus::Queued, JobStatus::Paused], JobStatus::Cancelled).await
+}
+
+/// 일시정지: 실행 중이면 다음 항목 전에 멈추고, 대기 중이면 대기열에서 뺌
+pub async fn pause(app: &AppHandle, id: &str) -> Result<Job, String> {
+    change_state(app, id, PAUSE, &[JobStatus::Queued], JobStatus::Paused).await
+}
+
+/// 재개: 일시정지/실패한 작업을 다시 대기열에 넣음 (이미 처리한 파일/Chunk는 건너뛰므로 이어서 진행됨)
+pub async fn resume(app: &AppHandle, id: &str) -> Result<Job, String> {
+    let state = app.state::<AppState>();
+    let job = get_job(&state.db, id).await.map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Job not found: {}", id))?;
+    if !matches!(job.status, JobStatus::Paused | JobStatus::Failed) {
+        return Err(format!("Job is {:?}, only paused or failed jobs can be resumed", job.status));
+    }
+
+    set_status(&state.db, id, JobStatus::Queued).await.map_err(|e| e.to_string())?;
+    state.jobs.wake.notify_one();
+    emit_job(app, id).await;
+    get_job(&state.db, id).await.map_err(|e| e.to_string())?.ok_or_else(|| format!("Job not found: {}", id))
+}
+
+async fn change_state(app: &AppHandle, id: &str, signal: u8, idle: &[JobStatus], next: JobStatus) -> Result<Job, String> {
+    let state = app.state::<AppState>();
+    let job = get_job(&state.db, id).await.map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Job not found: {}", id))?;
+
+    // 실행 중인 작업은 작업자가 멈춘 뒤 상태를 기록함
+    if state.jobs.signal(id, signal) {
+        return Ok(job);
+    }
+    if !idle.contains(&job.status) {
+        return Err(format!("Job is {:?}", job.status));
+    }
+
+    set_status(&state.db, id, next).await.map_err(|e| e.to_string())?;
+    emit_job(app, id).await;
+    get_job(&state.db, id).await.map_err(|e| e.to_string())?.ok_or_else(|| format!("Job not found: {}", id))
+}
+
+async fn set_status(db: &Surreal<Db>, id: &str, status: JobStatus) -> surrealdb::Result<()> {
+    let finished = matches!(status, JobStatus::Done | JobStatus::Failed | JobStatus::Cancelled);
+    db.query("UPDATE $id SET status = $status, finished_at = IF $finished THEN time::now() ELSE NONE END")
+        .bind(("id", job_thing(id)))
+        .bind(("status", status))
+        .bind(("finished", finished))
+        .await?.check()?;
+    Ok(())
+}
@@ -0,0 +1,91 @@
+// src/jobs/runner.rs
+
+use serde_json::json;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use super::{emit_job, set_status, Job, JobContext, JobControl, JobRequest, JobStatus, CANCEL, PAUSE};
+use crate::commands::embed::backfill_all;
+use crate::commands::ingest::{build_graph, run_ingest};
+use crate::watcher::apply_changes;
+use crate::AppState;
+
+/// 작업자들을 시작합니다. (setup에서 한 번 실행)
+pub async fn start(app: AppHandle) {
+    let state = app.state::<AppState>();
+
+    // 창을 닫아 중간에 끊긴 작업은 다시 대기열로 (처리한 파일/Chunk는 건너뛰므로 이어서 진행됨)
+    match state.db.query("UPDATE job SET status = 'queued' WHERE status = 'running'").await {
+        Ok(_) => {}
+        Err(e) => eprintln!("⚠️ [Job] 중단된 작업 복구 실패: {}", e),
+    }
+
+    let workers = state.settings().jobs.workers.max(1);
+    println!("🧵 [Job] Starting {} workers", workers);
+    for n in 0..workers {
+        tauri::async_runtime::spawn(worker(app.clone(), n));
+    }
+}
+
+async fn worker(app: AppHandle, n: usize) {
+    let state = app.state::<AppState>();
+    loop {
+        match state.jobs.claim(&state.db).await {
+            Ok(Some((job, control))) => execute(&app, n, job, control).await,
+            // 할 일이 없으면 submit/resume이 깨울 때까지 대기
+            Ok(None) => state.jobs.wake.notified().await,
+            Err(e) => {
+                eprintln!("⚠️ [Job] Worker #{} 대기열 조회 실패: {}", n, e);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+        }
+    }
+}
+
+async fn execute(app: &AppHandle, n: usize, job: Job, control: JobControl) {
+    let state = app.state::<AppState>();
+    let ctx = JobContext { id: job.id.clone(), app: app.clone(), control: control.clone() };
+    println!("\n🧵 [Job] Worker #{} running {} ({})", n, job.request.kind(), job.id);
+    emit_job(app, &job.id).await;
+
+    // 작업 중 panic이 나도 작업자는 계속 돌도록 별도 task로 실행
+    let result = match tauri::async_runtime::spawn(run(ctx, job.request.clone())).await {
+        Ok(result) => result,
+        Err(e) => Err(format!("Job panicked: {}", e)),
+    };
+    state.jobs.release(&job.id);
+
+    let (status, value, error) = match result {
+        Ok(value) => (JobStatus::Done, Some(value), None),
+        Err(e) => match control.requested() {
+            PAUSE => (JobStatus::Paused, None, None),
+            CANCEL => (JobStatus::Cancelled, None, None),
+            _ => (JobStatus::Failed, None, Some(e)),
+        },
+    };
+    println!("🧵 [Job] {} -> {:?}{}", job.id, status, error.as_deref().map(|e| format!(" ({})", e)).unwrap_or_default());
+
+    let saved = state.db.query("UPDATE $id SET result = $result, error = $error")
+        .bind(("id", super::job_thing(&job.id)))
+        .bind(("result", value))
+        .bind(("error", error))
+        .await;
+    if let Err(e) = saved {
+        eprintln!("⚠️ [Job] 결과 저장 실패: {}", e);
+    }
+    if let Err(e) = set_status(&state.db, &job.id, status).await {
+        eprintln!("⚠️ [Job] 상태 저장 실패: {}", e);
+    }
+    emit_job(app, &job.id).await;
+}
+
+/// 작업 종류별 실행 (결과는 job.result에 저장)
+async fn run(ctx: JobContext, request: JobRequest) -> Result<serde_json::Value, String> {
+    let state = ctx.app.state::<AppState>();
+    match request {
+        JobRequest::Ingest { path, chunking, scan } => run_ingest(&state, &ctx, &path, chunking, scan).await.map(|r| json!(r)),
+        JobRequest::Graph { mode } => build_graph(&state, &ctx, mode).await.map(|m| json!(m)),
+        JobRequest::Embed => backfill_all(&state, &ctx).await.map(|m| json!(m)),
+        JobRequest::Watch { root, paths } => apply_changes(&state, &ctx, &root, paths).await.map(|r| json!(r)),
+    }
+}
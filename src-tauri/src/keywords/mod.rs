@@ -0,0 +1,85 @@
+// src/keywords/mod.rs
+
+pub mod tokenize;
+pub mod tfidf;
+pub mod textrank;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use tfidf::Corpus;
+
+/// construct_graph에서 Entity 후보를 얻는 방식
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum KeywordMode {
+    /// LLM 분석 결과(key_entities)만 사용 (분석에 실패한 Chunk는 retry_failed_chunks 후에 연결)
+    #[default]
+    Llm,
+    /// LLM 분석 결과를 쓰고, 분석에 실패했거나 결과가 없는 Chunk는 통계 추출로 보충
+    Fallback,
+    /// LLM 없이 통계 추출(TF-IDF + TextRank)만 사용
+    Statistical,
+}
+
+/// 통계 키워드 추출 옵션
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct KeywordOptions {
+    pub mode: KeywordMode,
+    /// Chunk 하나에서 뽑을 최대 키워드 수
+    pub top_n: usize,
+    /// TextRank 동시 출현 윈도우 (단어 수)
+    pub window: usize,
+    /// 최종 점수에서 TextRank가 차지하는 비중 (나머지는 TF-IDF)
+    pub textrank_weight: f32,
+}
+
+impl Default for KeywordOptions {
+    fn default() -> Self {
+        Self { mode: KeywordMode::default(), top_n: 8, window: 4, textrank_weight: 0.5 }
+    }
+}
+
+/// 추출된 키워드와 점수 (0~1)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Keyword {
+    pub term: String,
+    pub score: f32,
+}
+
+/// Chunk 하나의 키워드를 추출합니다.
+/// TF-IDF(코퍼스 전체 기준 희귀도)와 TextRank(Chunk 안에서의 중심성)를 각각 최댓값으로 정규화해 섞습니다.
+pub fn extract_keywords(text: &str, corpus: &Corpus, opts: &KeywordOptions) -> Vec<Keyword> {
+    let pairs = tokenize::terms_with_surface(text);
+    if pairs.is_empty() { return vec![]; }
+
+    // 표시용 이름은 처음 나온 원문 표기
+    let mut surface: HashMap<&str, &str> = HashMap::new();
+    for (term, form) in &pairs {
+        surface.entry(term.as_str()).or_insert(form.as_str());
+    }
+    let tokens: Vec<String> = pairs.iter().map(|(term, _)| term.clone()).collect();
+
+    let tfidf = corpus.tfidf(&tokens);
+    let textrank = textrank::rank(&tokens, opts.window.max(2));
+
+    let max_of = |m: &HashMap<String, f32>| m.values().copied().fold(0.0f32, f32::max);
+    let (max_tfidf, max_tr) = (max_of(&tfidf), max_of(&textrank));
+    let weight = opts.textrank_weight.clamp(0.0, 1.0);
+
+    let mut keywords: Vec<Keyword> = tfidf.iter()
+        .map(|(term, &t)| {
+            let tr = textrank.get(term).copied().unwrap_or(0.0);
+            let t = if max_tfidf > 0.0 { t / max_tfidf } else { 0.0 };
+            let tr = if max_tr > 0.0 { tr / max_tr } else { 0.0 };
+            let display = surface.get(term.as_str()).copied().unwrap_or(term.as_str());
+            Keyword { term: display.to_string(), score: weight * tr + (1.0 - weight) * t }
+        })
+        .collect();
+
+    // 점수가 같으면 용어 순으로 (실행마다 결과가 같도록)
+    keywords.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.term.cmp(&b.term)));
+    keywords.truncate(opts.top_n);
+    keywords
+}
@@ -0,0 +1,50 @@
+// src/keywords/textrank.rs
+
+use std::collections::HashMap;
+
+const DAMPING: f32 = 0.85;
+const MAX_ITERATIONS: usize = 50;
+const CONVERGENCE: f32 = 1e-4;
+
+/// 단어 동시 출현 그래프에서 PageRank로 단어 중심성을 계산합니다.
+/// `window` 안에 함께 나온 단어끼리 무방향 간선으로 잇고, 함께 나온 횟수를 가중치로 사용합니다.
+pub fn rank(tokens: &[String], window: usize) -> HashMap<String, f32> {
+    // 단어 -> 정점 번호
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for t in tokens {
+        let next = index.len();
+        index.entry(t.as_str()).or_insert(next);
+    }
+    let n = index.len();
+    if n == 0 { return HashMap::new(); }
+
+    let mut edges: Vec<HashMap<usize, f32>> = vec![HashMap::new(); n];
+    for (i, a) in tokens.iter().enumerate() {
+        for b in tokens.iter().skip(i + 1).take(window.saturating_sub(1)) {
+            let (a, b) = (index[a.as_str()], index[b.as_str()]);
+            if a == b { continue; }
+            *edges[a].entry(b).or_default() += 1.0;
+            *edges[b].entry(a).or_default() += 1.0;
+        }
+    }
+    let out_weight: Vec<f32> = edges.iter().map(|e| e.values().sum()).collect();
+
+    let mut scores = vec![1.0f32; n];
+    for _ in 0..MAX_ITERATIONS {
+        let mut next = vec![1.0 - DAMPING; n];
+        for (from, neighbours) in edges.iter().enumerate() {
+            if out_weight[from] == 0.0 { continue; }
+            for (&to, &w) in neighbours {
+                next[to] += DAMPING * scores[from] * w / out_weight[from];
+            }
+        }
+
+        let delta: f32 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if delta < CONVERGENCE { break; }
+    }
+
+    index.into_iter()
+        .map(|(term, i)| (term.to_string(), scores[i]))
+        .collect()
+}
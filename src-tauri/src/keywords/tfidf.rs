@@ -0,0 +1,52 @@
+// src/keywords/tfidf.rs
+
+use std::collections::{HashMap, HashSet};
+
+use super::tokenize;
+
+/// 코퍼스 전체의 문서 빈도 (IDF 계산용)
+#[derive(Debug, Default)]
+pub struct Corpus {
+    doc_count: usize,
+    doc_freq: HashMap<String, usize>,
+}
+
+impl Corpus {
+    /// 텍스트(보통 모든 Chunk 내용) 목록으로 문서 빈도를 셉니다.
+    pub fn build<'a>(texts: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut corpus = Self::default();
+        for text in texts {
+            corpus.add(text);
+        }
+        corpus
+    }
+
+    pub fn add(&mut self, text: &str) {
+        self.doc_count += 1;
+        let unique: HashSet<String> = tokenize::terms(text).into_iter().collect();
+        for term in unique {
+            *self.doc_freq.entry(term).or_default() += 1;
+        }
+    }
+
+    /// 부드럽게 만든 IDF: ln((N + 1) / (df + 1)) + 1 (코퍼스에 없는 단어도 0이 되지 않음)
+    pub fn idf(&self, term: &str) -> f32 {
+        let df = self.doc_freq.get(term).copied().unwrap_or(0);
+        ((self.doc_count as f32 + 1.0) / (df as f32 + 1.0)).ln() + 1.0
+    }
+
+    /// 단어 목록의 TF-IDF (TF = 등장 횟수 / 전체 단어 수)
+    pub fn tfidf(&self, tokens: &[String]) -> HashMap<String, f32> {
+        let mut tf: HashMap<String, f32> = HashMap::new();
+        for t in tokens {
+            *tf.entry(t.clone()).or_default() += 1.0;
+        }
+        let total = tokens.len().max(1) as f32;
+        tf.into_iter()
+            .map(|(term, count)| {
+                let score = count / total * self.idf(&term);
+                (term, score)
+            })
+            .collect()
+    }
+}
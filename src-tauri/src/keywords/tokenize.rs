@@ -0,0 +1,65 @@
+// src/keywords/tokenize.rs
+
+use crate::resolution::normalize::normalize_entity_name;
+
+/// 키워드가 될 수 없는 흔한 단어 (영문은 소문자)
+const STOPWORDS: &[&str] = &[
+    // English
+    "the", "and", "for", "are", "but", "not", "you", "all", "any", "can", "had", "her", "was", "one",
+    "our", "out", "has", "have", "his", "how", "its", "may", "new", "now", "see", "who", "did", "get",
+    "use", "used", "using", "this", "that", "with", "from", "they", "will", "would", "there", "their",
+    "what", "when", "where", "which", "while", "about", "into", "than", "then", "them", "these",
+    "those", "also", "such", "been", "were", "more", "most", "some", "only", "other", "each", "very",
+    "can't", "should", "could", "between", "through", "over", "under", "after", "before",
+    // 한국어 (조사를 뗀 뒤 기준)
+    "그리고", "그러나", "하지만", "그래서", "또한", "따라서", "때문", "대한", "대해", "통해", "위해",
+    "있다", "있는", "있으며", "없는", "하는", "한다", "했다", "된다", "되는", "이다", "입니다", "합니다",
+    "우리", "이것", "그것", "저것", "여기", "거기", "경우", "정도", "관련", "다음", "이번", "모든",
+    "각각", "가장", "매우", "이후", "이전", "현재", "사용", "것이", "수도", "등의",
+];
+
+/// 동사/형용사로 끝나는 한글 어절 (명사가 아니므로 제외)
+const VERB_ENDINGS: &[&str] = &[
+    "한다", "된다", "는다", "했다", "됐다", "었다", "았다", "였다", "하다", "든다", "간다", "온다",
+    "진다", "준다", "하며", "하고", "되어", "하여", "해서", "있다", "없다",
+];
+
+/// 키워드 후보 단어 목록 (문서 순서 유지)
+/// 한글 어절은 조사를 떼고 2글자 이상, 영문/숫자 단어는 소문자로 3글자 이상(숫자만 있는 것 제외)
+pub fn terms(text: &str) -> Vec<String> {
+    terms_with_surface(text).into_iter().map(|(term, _)| term).collect()
+}
+
+/// (비교용 단어, 원문 표기) 목록. 원문 표기는 조사만 뗀 것 (예: ("openai", "OpenAI"))
+pub fn terms_with_surface(text: &str) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    for word in text.split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '-')) {
+        let word = word.trim_matches(|c: char| c == '\'' || c == '-');
+        if word.is_empty() { continue; }
+
+        // "OpenAI의"처럼 영문 뒤에 조사가 붙은 경우도 함께 처리
+        let normalized = normalize_entity_name(word);
+        let term = if normalized.chars().any(is_hangul) { normalized.clone() } else { normalized.to_lowercase() };
+
+        if is_candidate(&term) {
+            out.push((term, normalized));
+        }
+    }
+    out
+}
+
+fn is_candidate(term: &str) -> bool {
+    if STOPWORDS.contains(&term) { return false; }
+    if term.chars().all(|c| c.is_numeric() || c == '-') { return false; }
+
+    let len = term.chars().count();
+    if term.chars().any(is_hangul) {
+        len >= 2 && !VERB_ENDINGS.iter().any(|e| term.ends_with(e))
+    } else {
+        len >= 3
+    }
+}
+
+fn is_hangul(c: char) -> bool {
+    ('\u{AC00}'..='\u{D7A3}').contains(&c)
+}
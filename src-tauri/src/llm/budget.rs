@@ -0,0 +1,199 @@
+// src/llm/budget.rs
+
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::LlmEndpoint;
+use crate::utils::{estimate_tokens, is_wide_char};
+
+/// 채팅 템플릿이 메시지마다 덧붙이는 토큰(역할 태그 등) 추정치
+const MESSAGE_OVERHEAD_TOKENS: usize = 8;
+/// 남은 예산이 이보다 적으면 잘라 넣지 않고 통째로 제외 (의미 없는 조각 방지)
+const MIN_FIT_TOKENS: usize = 32;
+
+/// llama-server의 /tokenize, /detokenize로 토큰을 셉니다.
+/// 서버 호출이 실패하면 이후로는 utils::estimate_tokens 추정치로 대신합니다.
+pub struct Tokenizer {
+    client: Client,
+    /// /v1을 뺀 서버 주소 (http://host:port)
+    server_url: String,
+    use_estimate: AtomicBool,
+}
+
+impl Tokenizer {
+    pub fn for_endpoint(endpoint: &LlmEndpoint) -> Self {
+        let base = endpoint.base_url.trim_end_matches('/');
+        Self {
+            client: Client::new(),
+            server_url: base.strip_suffix("/v1").unwrap_or(base).to_string(),
+            use_estimate: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn count(&self, text: &str) -> usize {
+        if text.is_empty() { return 0; }
+        match self.server_tokens(text).await {
+            Some(tokens) => tokens.len(),
+            None => estimate_tokens(text),
+        }
+    }
+
+    /// 앞에서부터 최대 `max_tokens`토큰만 남깁니다. (토큰 경계에서 자르므로 UTF-8 문자가 깨지지 않음)
+    /// 반환: (잘린 텍스트, 남긴 토큰 수, 원래 토큰 수)
+    pub async fn truncate(&self, text: &str, max_tokens: usize) -> (String, usize, usize) {
+        if let Some(tokens) = self.server_tokens(text).await {
+            let total = tokens.len();
+            if total <= max_tokens { return (text.to_string(), total, total); }
+
+            if let Some(content) = self.detokenize(&tokens[..max_tokens]).await {
+                // 멀티바이트 문자 중간에서 끝난 토큰은 U+FFFD로 돌아오므로 제거
+                return (content.trim_end_matches('\u{FFFD}').to_string(), max_tokens, total);
+            }
+        }
+
+        let total = estimate_tokens(text);
+        if total <= max_tokens { return (text.to_string(), total, total); }
+        let kept = truncate_estimated(text, max_tokens);
+        let kept_tokens = estimate_tokens(&kept);
+        (kept, kept_tokens, total)
+    }
+
+    async fn server_tokens(&self, text: &str) -> Option<Vec<Value>> {
+        if self.use_estimate.load(Ordering::Relaxed) { return None; }
+
+        let res = self.request("tokenize", json!({ "content": text, "add_special": false })).await;
+        match res {
+            Ok(body) => body["tokens"].as_array().cloned(),
+            Err(e) => {
+                println!("    ⚠️ /tokenize failed, using estimated token counts: {}", e);
+                self.use_estimate.store(true, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    async fn detokenize(&self, tokens: &[Value]) -> Option<String> {
+        let body = self.request("detokenize", json!({ "tokens": tokens })).await.ok()?;
+        body["content"].as_str().map(|s| s.to_string())
+    }
+
+    async fn request(&self, path: &str, payload: Value) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/{}", self.server_url, path);
+        let res = self.client.post(&url).json(&payload).send().await?;
+        if !res.status().is_success() {
+            return Err(format!("{} Request Failed: {}", path, res.status()).into());
+        }
+        Ok(res.json().await?)
+    }
+}
+
+/// 예산에서 빠졌거나 잘린 항목
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DroppedText {
+    pub label: String,
+    pub total_tokens: usize,
+    /// 0이면 통째로 제외됨
+    pub kept_tokens: usize,
+}
+
+/// 프롬프트 예산 사용 결과
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetReport {
+    pub ctx_size: usize,
+    pub reserved_output: usize,
+    pub used_tokens: usize,
+    pub dropped: Vec<DroppedText>,
+}
+
+impl BudgetReport {
+    pub fn log(&self) {
+        for d in &self.dropped {
+            println!("    ✂️ Prompt budget: \"{}\" kept {}/{} tokens", d.label, d.kept_tokens, d.total_tokens);
+        }
+    }
+}
+
+/// ctx-size 안에 시스템 프롬프트, 컨텍스트, 질문을 채워 넣습니다.
+/// 필수 항목(`add_message`)을 먼저 차감하고, 남은 예산만큼 선택 항목(`fit`)을 앞에서부터 채웁니다.
+pub struct PromptBudget<'a> {
+    tokenizer: &'a Tokenizer,
+    limit: usize,
+    report: BudgetReport,
+}
+
+impl<'a> PromptBudget<'a> {
+    /// `reserved_output`: 답변 생성용으로 비워둘 토큰 수
+    pub fn new(tokenizer: &'a Tokenizer, ctx_size: usize, reserved_output: usize) -> Self {
+        Self {
+            tokenizer,
+            limit: ctx_size.saturating_sub(reserved_output),
+            report: BudgetReport { ctx_size, reserved_output, ..Default::default() },
+        }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.limit.saturating_sub(self.report.used_tokens)
+    }
+
+    /// 반드시 들어가는 메시지 (잘라내지 않음)
+    pub async fn add_message(&mut self, text: &str) -> usize {
+        let tokens = self.tokenizer.count(text).await + MESSAGE_OVERHEAD_TOKENS;
+        self.report.used_tokens += tokens;
+        tokens
+    }
+
+    /// 반드시 들어가는 짧은 텍스트 (컨텍스트 항목의 머리말 등)
+    pub async fn add(&mut self, text: &str) -> usize {
+        let tokens = self.tokenizer.count(text).await;
+        self.report.used_tokens += tokens;
+        tokens
+    }
+
+    /// 남은 예산에 맞춰 자른 텍스트를 반환합니다. 넣을 자리가 없으면 None (보고서에 기록됨)
+    pub async fn fit(&mut self, label: &str, text: &str) -> Option<String> {
+        let remaining = self.remaining();
+        if remaining < MIN_FIT_TOKENS {
+            let total = self.tokenizer.count(text).await;
+            self.report.dropped.push(DroppedText { label: label.to_string(), total_tokens: total, kept_tokens: 0 });
+            return None;
+        }
+
+        let (kept, kept_tokens, total) = self.tokenizer.truncate(text, remaining).await;
+        self.report.used_tokens += kept_tokens;
+        if kept_tokens < total {
+            self.report.dropped.push(DroppedText { label: label.to_string(), total_tokens: total, kept_tokens });
+        }
+        Some(kept)
+    }
+
+    /// 머리말이 붙는 선택 항목 (참고 문서 등): 본문이 들어갈 때만 머리말도 차감
+    pub async fn fit_with_header(&mut self, header: &str, text: &str) -> Option<String> {
+        let header_tokens = self.add(header).await;
+        let fitted = self.fit(header, text).await;
+        if fitted.is_none() {
+            self.report.used_tokens -= header_tokens;
+        }
+        fitted
+    }
+
+    pub fn finish(self) -> BudgetReport {
+        self.report
+    }
+}
+
+/// estimate_tokens 기준으로 `max_tokens`를 넘지 않는 앞부분 (글자 단위로 자르므로 UTF-8 안전)
+fn truncate_estimated(text: &str, max_tokens: usize) -> String {
+    let (mut wide, mut other) = (0usize, 0usize);
+    for (i, c) in text.char_indices() {
+        if is_wide_char(c) { wide += 1 } else { other += 1 }
+        if wide + other.div_ceil(4) > max_tokens {
+            return text[..i].to_string();
+        }
+    }
+    text.to_string()
+}
@@ -0,0 +1,105 @@
+// src/llm/chat.rs
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::error::Error;
+
+use super::LlmEndpoint;
+
+/// OpenAI 호환 채팅 메시지
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn system(content: impl Into<String>) -> Self {
+        Self { role: "system".into(), content: content.into() }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self { role: "user".into(), content: content.into() }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self { role: "assistant".into(), content: content.into() }
+    }
+}
+
+/// `/chat/completions`를 스트리밍(SSE)으로 호출하고, 토큰이 올 때마다 `on_token`을 호출합니다.
+/// 완료되면 전체 응답 문자열을 반환합니다.
+pub async fn stream_chat<F>(
+    endpoint: &LlmEndpoint,
+    messages: &[ChatMessage],
+    temperature: f32,
+    mut on_token: F,
+) -> Result<String, Box<dyn Error + Send + Sync>>
+where
+    F: FnMut(&str),
+{
+    let payload = json!({
+        "model": endpoint.model,
+        "messages": messages,
+        "temperature": temperature,
+        "stream": true,
+    });
+
+    let url = format!("{}/chat/completions", endpoint.base_url.trim_end_matches('/'));
+    let mut res = Client::new().post(&url).json(&payload).send().await?;
+    if !res.status().is_success() {
+        return Err(format!("LLM Request Failed: {}", res.status()).into());
+    }
+
+    let mut full = String::new();
+    // 네트워크 청크 경계가 줄/UTF-8 문자 중간일 수 있으므로 바이트 단위로 모았다가 줄 단위로 처리
+    let mut buffer: Vec<u8> = Vec::new();
+
+    while let Some(bytes) = res.chunk().await? {
+        buffer.extend_from_slice(&bytes);
+
+        while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let Some(data) = line.trim().strip_prefix("data:") else { continue };
+            let data = data.trim();
+            if data == "[DONE]" { return Ok(full); }
+
+            if let Ok(event) = serde_json::from_str::<Value>(data) {
+                if let Some(token) = event["choices"][0]["delta"]["content"].as_str() {
+                    if !token.is_empty() {
+                        full.push_str(token);
+                        on_token(token);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(full)
+}
+
+/// `/chat/completions`를 한 번에(비스트리밍) 호출하고 응답 문자열을 반환합니다.
+pub async fn complete_chat(
+    endpoint: &LlmEndpoint,
+    messages: &[ChatMessage],
+    temperature: f32,
+    max_tokens: usize,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let payload = json!({
+        "model": endpoint.model,
+        "messages": messages,
+        "temperature": temperature,
+        "max_tokens": max_tokens,
+    });
+
+    let url = format!("{}/chat/completions", endpoint.base_url.trim_end_matches('/'));
+    let res = Client::new().post(&url).json(&payload).send().await?;
+    if !res.status().is_success() {
+        return Err(format!("LLM Request Failed: {}", res.status()).into());
+    }
+
+    let resp_json: Value = res.json().await?;
+    Ok(resp_json["choices"][0]["message"]["content"].as_str().unwrap_or("").to_string())
+}
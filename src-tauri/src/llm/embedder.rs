@@ -0,0 +1,62 @@
+// src/llm/embedder.rs
+
+use rig::client::EmbeddingsClient;
+use rig::embeddings::EmbeddingModel;
+use rig::providers::openai::Client as OpenAiClient;
+use std::error::Error;
+
+/// llama-server는 모델명을 무시하지만 OpenAI 호환 요청 형식상 필요합니다.
+pub const EMBED_MODEL_NAME: &str = "ggml-model-Q4_K_M";
+
+/// 한 번의 요청으로 임베딩 서버에 보낼 텍스트 개수
+pub const EMBED_BATCH_SIZE: usize = 16;
+
+/// 임베딩 서버(--ctx-size 2048)를 넘지 않도록 입력 글자 수를 제한합니다.
+const EMBED_MAX_CHARS: usize = 1500;
+
+/// 여러 텍스트를 배치로 나누어 임베딩 서버(8080)에 보내고, 입력 순서대로 벡터를 반환합니다.
+pub async fn embed_texts(
+    client: &OpenAiClient,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, Box<dyn Error + Send + Sync>> {
+    let model = client.embedding_model(EMBED_MODEL_NAME);
+    let mut vectors = Vec::with_capacity(texts.len());
+
+    for batch in texts.chunks(EMBED_BATCH_SIZE) {
+        // 글자 단위로 자르므로 한글(멀티바이트)에서도 안전합니다.
+        let inputs: Vec<String> = batch
+            .iter()
+            .map(|t| t.chars().take(EMBED_MAX_CHARS).collect())
+            .collect();
+
+        let embeddings = model.embed_texts(inputs).await?;
+        if embeddings.len() != batch.len() {
+            return Err(format!(
+                "Embedding count mismatch: sent {}, received {}",
+                batch.len(),
+                embeddings.len()
+            )
+            .into());
+        }
+
+        vectors.extend(
+            embeddings
+                .into_iter()
+                .map(|e| e.vec.into_iter().map(|v| v as f32).collect::<Vec<f32>>()),
+        );
+    }
+
+    Ok(vectors)
+}
+
+/// 단일 텍스트(검색 질의 등)를 임베딩합니다.
+pub async fn embed_query(
+    client: &OpenAiClient,
+    text: &str,
+) -> Result<Vec<f32>, Box<dyn Error + Send + Sync>> {
+    embed_texts(client, &[text.to_string()])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Empty embedding response".into())
+}
@@ -1,113 +1,127 @@
-use crate::models::CoreAnalysisResult;
 use serde_json::{json, Value};
-use std::error::Error;
-use reqwest::Client;
-use regex::Regex;
+
+use crate::models::{CoreAnalysisResult, KeyEntity};
+use super::budget::{PromptBudget, Tokenizer};
+use super::chat::ChatMessage;
+use super::structured::{extract_with_schema, schema_of, ExtractionError, RetryPolicy};
+use super::LlmEndpoint;
+
+/// JSON 응답 생성용으로 비워둘 토큰 수
+const ANALYSIS_OUTPUT_TOKENS: usize = 768;
+
+/// 어느 분류에도 맞지 않는 Entity의 분류 (설정에 없어도 항상 허용)
+const OTHER_CATEGORY: &str = "Other";
 
 /// 텍스트를 분석하여 구조화된 JSON(CoreAnalysisResult)으로 반환합니다.
+/// 응답이 스키마와 맞지 않으면 원인을 담은 ExtractionError를 반환합니다.
 /// (Ingest Step 1에서 사용)
 pub async fn analyze_content(
-    base_url: &str,
+    endpoint: &LlmEndpoint,
+    policy: &RetryPolicy,
+    categories: &[String],
     text: &str
-) -> Result<CoreAnalysisResult, Box<dyn Error + Send + Sync>> {
-    let client = Client::new();
-    let model_name = "gpt-3.5-turbo"; 
+) -> Result<CoreAnalysisResult, ExtractionError> {
+    let categories = &allowed_categories(categories);
 
     // 프롬프트: 단순 요약이 아닌 "구조화된 정보" 추출 요구
-    let system_instruction = r#"
+    let system_instruction = format!(r#"
     You are a Data Analyst preparing data for a Knowledge Graph.
     Analyze the given text and extract core information into JSON.
 
     ### JSON Output Format ###
-    {
+    {{
         "topic": "A short, descriptive title for this segment",
         "summary": "Contextual summary in Korean (1-2 sentences)",
-        "key_entities": ["List", "of", "important", "nouns", "or", "names"],
-        "detailed_data": {
+        "key_entities": [
+            {{ "name": "Entity name as written", "type": "One of the allowed types", "description": "One short sentence in Korean" }}
+        ],
+        "detailed_data": {{
             "type": "Identify the text type (e.g., Code, Meeting, News, Paper)",
             "facts": ["List of key facts"],
             "sentiment": "Neutral/Positive/Negative (Optional)"
-        }
-    }
+        }}
+    }}
 
     ### RULES ###
     1. Output MUST be valid JSON.
-    2. 'summary' and 'facts' MUST be in **Korean**.
-    3. 'key_entities' should be potential nodes for a graph (Person, Tech, Location).
-    "#;
-
-    // 텍스트 길이 제한 (속도 및 토큰 비용 최적화)
-    let truncated_text = if text.len() > 3000 { &text[0..3000] } else { text };
-
-    let payload = json!({
-        "model": model_name,
-        "messages": [
-            { "role": "system", "content": system_instruction },
-            { "role": "user", "content": truncated_text }
-        ],
-        "temperature": 0.2, // 구조화 정확성을 위해 낮음
-        "response_format": { "type": "json_object" }
-    });
-
-    let endpoint = format!("{}/chat/completions", base_url.trim_end_matches('/'));
-    
-    let res = client.post(&endpoint).json(&payload).send().await?;
-    if !res.status().is_success() {
-        return Err(format!("LLM Request Failed: {}", res.status()).into());
+    2. 'summary', 'facts' and entity 'description' MUST be in **Korean**.
+    3. 'key_entities' should be potential nodes for a graph.
+    4. Entity 'type' MUST be one of: {}.
+    "#, categories.join(", "));
+
+    // 컨텍스트 크기에 맞춰 입력을 토큰 단위로 자름 (넘치는 부분은 로그로 보고)
+    let tokenizer = Tokenizer::for_endpoint(endpoint);
+    let mut budget = PromptBudget::new(&tokenizer, endpoint.ctx_size, ANALYSIS_OUTPUT_TOKENS);
+    budget.add_message(&system_instruction).await;
+    budget.add_message("").await; // user 메시지의 템플릿 토큰
+    let truncated_text = budget.fit("analysis input", text).await;
+    budget.finish().log();
+    // 입력 없이 요청하면 빈 텍스트를 분석한 결과가 저장되므로 실패로 처리
+    let truncated_text = truncated_text.ok_or_else(|| ExtractionError::PromptTooLong {
+        message: "no room left for the analysis input".to_string(),
+    })?;
+
+    let messages = [
+        ChatMessage::system(system_instruction),
+        ChatMessage::user(truncated_text),
+    ];
+
+    // Entity type을 설정된 분류 목록으로 제한
+    let mut schema = schema_of::<CoreAnalysisResult>();
+    schema["properties"]["key_entities"]["items"]["properties"]["type"]["enum"] = json!(categories);
+
+    // 스키마로 출력 형식을 제한하고(json_schema), 응답도 같은 스키마로 검증
+    // 구조화 정확성을 위해 temperature는 낮게
+    let mut result: CoreAnalysisResult =
+        extract_with_schema(endpoint, "core_analysis", schema, &messages, 0.2, policy).await?;
+
+    // 대소문자만 다른 분류는 설정값 표기로 통일
+    for entity in &mut result.key_entities {
+        entity.entity_type = normalize_category(&entity.entity_type, categories);
     }
-
-    let resp_json: Value = res.json().await?;
-    let content = resp_json["choices"][0]["message"]["content"].as_str().unwrap_or("{}");
-
-    // JSON 수리 (LLM이 가끔 깨진 JSON을 줄 때를 대비)
-    let cleaned = clean_and_repair_json(content);
-    
-    // 파싱 시도, 실패 시 에러 정보를 담은 기본 객체 반환
-    let result: CoreAnalysisResult = serde_json::from_str(&cleaned).unwrap_or(CoreAnalysisResult {
-        topic: "Analysis Failed".to_string(),
-        summary: "분석 실패".to_string(),
-        key_entities: vec![],
-        detailed_data: json!({ "error": "Parsing failed", "raw": content }),
-    });
-
     Ok(result)
 }
 
-/// LLM 응답 문자열에서 깨진 JSON을 수리합니다.
-/// (Markdown 제거, Trailing Comma 제거, 닫히지 않은 괄호 수리)
-fn clean_and_repair_json(input: &str) -> String {
-    let mut clean = input.trim().to_string();
-
-    // 1. 마크다운 코드 블록 제거
-    if let Some(start) = clean.find("```json") { clean = clean[start+7..].to_string(); }
-    else if let Some(start) = clean.find("```") { clean = clean[start+3..].to_string(); }
-    if let Some(end) = clean.rfind("```") { clean = clean[..end].to_string(); }
-    
-    clean = clean.trim().to_string();
-
-    // 2. Trailing Comma 제거 (", ]" -> "]")
-    let re_trailing = Regex::new(r",(\s*[\]}])").unwrap();
-    clean = re_trailing.replace_all(&clean, "$1").to_string();
-
-    // 3. 빈 키 제거 ("": "",)
-    let re_empty_key = Regex::new(r#"\s*""\s*:\s*".*?",?"#).unwrap();
-    clean = re_empty_key.replace_all(&clean, "").to_string();
-    
-    // 4. 닫히지 않은 괄호 수리 (Truncated JSON 응급처치)
-    if !clean.ends_with('}') {
-        clean = clean.trim_end_matches(',').trim().to_string();
-        
-        let open_braces = clean.chars().filter(|&c| c == '{').count();
-        let close_braces = clean.chars().filter(|&c| c == '}').count();
-        let open_brackets = clean.chars().filter(|&c| c == '[').count();
-        let close_brackets = clean.chars().filter(|&c| c == ']').count();
-
-        if open_brackets > close_brackets { clean.push_str("]"); }
-        if open_braces > close_braces { clean.push_str("}"); }
-        
-        // 최후의 수단
-        if !clean.ends_with('}') { clean.push_str("}"); }
+/// 설정된 분류 목록 + "Other" (빈 항목 제외, 이미 있으면 추가하지 않음)
+/// normalize_category가 돌려주는 "Other"가 스키마 enum에서 벗어나지 않도록
+fn allowed_categories(categories: &[String]) -> Vec<String> {
+    let mut allowed: Vec<String> = categories.iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect();
+    if !allowed.iter().any(|c| c.eq_ignore_ascii_case(OTHER_CATEGORY)) {
+        allowed.push(OTHER_CATEGORY.to_string());
     }
+    allowed
+}
 
-    clean
-}
\ No newline at end of file
+/// 분류 목록에서 대소문자 무시로 일치하는 항목을 찾고, 없으면 "Other"
+pub fn normalize_category(category: &str, categories: &[String]) -> String {
+    let category = category.trim();
+    categories.iter()
+        .find(|c| c.eq_ignore_ascii_case(category))
+        .cloned()
+        .unwrap_or_else(|| OTHER_CATEGORY.to_string())
+}
+
+/// 저장된 분석 결과(metadata.analysis)에서 key_entities를 읽습니다.
+/// 이전 형식(이름 문자열 목록)은 분류 "Other", 설명 없음으로 취급합니다.
+pub fn key_entities_from(analysis: &Value) -> Vec<KeyEntity> {
+    let Some(arr) = analysis.get("key_entities").and_then(|v| v.as_array()) else { return vec![] };
+    arr.iter()
+        .filter_map(|e| match e {
+            Value::String(name) => Some(KeyEntity {
+                name: name.clone(),
+                entity_type: OTHER_CATEGORY.to_string(),
+                description: String::new(),
+            }),
+            _ => serde_json::from_value::<KeyEntity>(e.clone()).ok(),
+        })
+        .map(|mut e| {
+            e.name = e.name.trim().to_string();
+            e.description = e.description.trim().to_string();
+            e
+        })
+        .filter(|e| !e.name.is_empty())
+        .collect()
+}
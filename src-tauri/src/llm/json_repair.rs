@@ -0,0 +1,381 @@
+// src/llm/json_repair.rs
+
+use serde::Serialize;
+
+/// 수리 과정에서 적용한 작업
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Repair {
+    /// JSON 앞뒤의 설명 문장/마크다운 코드 블록 제거
+    StrippedText,
+    /// 닫는 괄호 앞의 쉼표 제거
+    RemovedTrailingComma,
+    /// 문자열 안의 줄바꿈/탭 등 제어 문자를 이스케이프
+    EscapedControlChar,
+    /// 작은따옴표 문자열을 큰따옴표 문자열로 바꿈 (`'a'` -> `"a"`)
+    ConvertedSingleQuotes,
+    /// 따옴표 없는 key를 감쌈 (`{name: 1}` -> `{"name": 1}`)
+    QuotedKey,
+    /// 끝나지 않은 문자열을 닫음
+    ClosedString,
+    /// 잘린 true/false/null 또는 숫자를 완성
+    CompletedLiteral,
+    /// 값이 없는 마지막 key 제거 (`"key":` 또는 `"ke`)
+    DroppedIncompleteMember,
+    /// 짝이 없는 닫는 괄호 제거
+    RemovedStrayCloser,
+    /// 열린 채로 끝난 객체/배열을 닫음
+    ClosedStructure,
+}
+
+/// 수리된 JSON 문자열 + 적용한 수리 목록
+#[derive(Debug, Clone)]
+pub struct Repaired {
+    pub json: String,
+    pub repairs: Vec<Repair>,
+}
+
+/// 객체 안에서 현재 멤버의 진행 상태
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MemberState {
+    /// '{' 또는 ',' 직후
+    Empty,
+    /// key 문자열을 읽는 중이거나 읽은 뒤 ':'를 기다리는 중
+    Key,
+    /// ':' 뒤, 값을 기다리는 중
+    Colon,
+    /// 값이 시작됨
+    Value,
+}
+
+struct Frame {
+    closer: char,
+    /// 현재 멤버가 시작된 출력 위치 (멤버가 불완전하면 여기까지 잘라냄, 앞의 쉼표 포함)
+    member_start: usize,
+    state: MemberState,
+}
+
+/// 잘리거나 형식이 어긋난 LLM 출력 JSON을 수리합니다.
+/// 문자열/이스케이프 상태와 괄호 스택을 따라가므로 문자열 안의 괄호는 무시하고,
+/// 끝에서는 실제로 열려 있는 구조만 순서대로 닫습니다.
+/// 작은따옴표 문자열과 따옴표 없는 key도 표준 JSON 표기로 바꿉니다.
+/// JSON 시작('{' 또는 '[')을 찾지 못하면 입력을 그대로 반환합니다.
+pub fn repair_json(input: &str) -> Repaired {
+    let mut repairs = Vec::new();
+
+    let Some(start) = input.find(['{', '[']) else {
+        return Repaired { json: input.trim().to_string(), repairs };
+    };
+    if !input[..start].trim().is_empty() {
+        repairs.push(Repair::StrippedText);
+    }
+
+    let mut out = String::with_capacity(input.len() - start + 8);
+    let mut stack: Vec<Frame> = Vec::new();
+    // 열려 있는 문자열의 따옴표 (출력은 항상 '"')
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    // 따옴표 없는 key를 읽는 중 (여는 '"'는 이미 출력함)
+    let mut bare_key = false;
+    let mut rest = "";
+
+    let body = &input[start..];
+    for (i, c) in body.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+                // 작은따옴표 문자열의 \' 는 JSON에 없는 이스케이프이므로 따옴표만 남김
+                if q == '\'' && c == '\'' { out.pop(); }
+                out.push(c);
+            } else if c == '\\' {
+                escaped = true;
+                out.push(c);
+            } else if c == q {
+                quote = None;
+                out.push('"');
+            } else if c == '"' {
+                // 작은따옴표 문자열 안의 큰따옴표
+                out.push_str("\\\"");
+            } else if c.is_control() {
+                push_escaped_control(&mut out, c);
+                push_once(&mut repairs, Repair::EscapedControlChar);
+            } else {
+                out.push(c);
+            }
+            continue;
+        }
+
+        if bare_key {
+            if is_bare_key_char(c) {
+                out.push(c);
+                continue;
+            }
+            out.push('"');
+            bare_key = false;
+        }
+
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                if c == '\'' { push_once(&mut repairs, Repair::ConvertedSingleQuotes); }
+                if let Some(frame) = stack.last_mut() {
+                    frame.state = match (frame.closer, frame.state) {
+                        ('}', MemberState::Empty) => MemberState::Key,
+                        _ => MemberState::Value,
+                    };
+                }
+                out.push('"');
+            }
+            c if (c.is_alphabetic() || c == '_')
+                && stack.last().is_some_and(|f| f.closer == '}' && f.state == MemberState::Empty) =>
+            {
+                bare_key = true;
+                if let Some(frame) = stack.last_mut() { frame.state = MemberState::Key; }
+                out.push('"');
+                out.push(c);
+                push_once(&mut repairs, Repair::QuotedKey);
+            }
+            '{' | '[' => {
+                mark_value(&mut stack);
+                out.push(c);
+                stack.push(Frame {
+                    closer: if c == '{' { '}' } else { ']' },
+                    member_start: out.len(),
+                    state: MemberState::Empty,
+                });
+            }
+            '}' | ']' => {
+                if !stack.iter().any(|f| f.closer == c) {
+                    push_once(&mut repairs, Repair::RemovedStrayCloser);
+                    continue;
+                }
+                // 안쪽에 열린 구조가 남아 있으면 먼저 닫음 (예: {"a": [1, 2} -> {"a": [1, 2]})
+                while stack.last().is_some_and(|f| f.closer != c) {
+                    close_frame(&mut out, &mut stack, &mut repairs);
+                    push_once(&mut repairs, Repair::ClosedStructure);
+                }
+                close_frame(&mut out, &mut stack, &mut repairs);
+
+                if stack.is_empty() {
+                    rest = &body[i + c.len_utf8()..];
+                    break;
+                }
+            }
+            ':' => {
+                if let Some(frame) = stack.last_mut() {
+                    if frame.state == MemberState::Key { frame.state = MemberState::Colon; }
+                }
+                out.push(c);
+            }
+            ',' => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.member_start = out.len();
+                    frame.state = MemberState::Empty;
+                }
+                out.push(c);
+            }
+            c if c.is_whitespace() => out.push(c),
+            _ => {
+                mark_value(&mut stack);
+                out.push(c);
+            }
+        }
+    }
+
+    if !rest.trim().is_empty() {
+        push_once(&mut repairs, Repair::StrippedText);
+    }
+
+    // 입력이 문자열 중간에서 끝난 경우 (key 중간이면 아래에서 멤버째 잘라냄)
+    if quote.is_some() {
+        if escaped { out.pop(); }
+        strip_partial_unicode_escape(&mut out);
+        out.push('"');
+        repairs.push(Repair::ClosedString);
+    }
+
+    // 열려 있는 구조를 안쪽부터 닫음
+    while !stack.is_empty() {
+        close_frame(&mut out, &mut stack, &mut repairs);
+        push_once(&mut repairs, Repair::ClosedStructure);
+    }
+
+    Repaired { json: out, repairs }
+}
+
+fn is_bare_key_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '$')
+}
+
+/// 현재 프레임에서 값이 시작됨
+fn mark_value(stack: &mut [Frame]) {
+    if let Some(frame) = stack.last_mut() {
+        frame.state = MemberState::Value;
+    }
+}
+
+/// 맨 위 프레임을 닫습니다. 마지막 멤버가 불완전하면 잘라내고, 끝의 쉼표는 제거합니다.
+fn close_frame(out: &mut String, stack: &mut Vec<Frame>, repairs: &mut Vec<Repair>) {
+    let Some(frame) = stack.pop() else { return };
+    truncate_whitespace(out);
+
+    let incomplete = match frame.state {
+        MemberState::Key | MemberState::Colon => frame.closer == '}',
+        MemberState::Value => !complete_literal(out, repairs),
+        MemberState::Empty => false,
+    };
+    if incomplete {
+        out.truncate(frame.member_start);
+        push_once(repairs, Repair::DroppedIncompleteMember);
+        truncate_whitespace(out);
+    }
+
+    if out.ends_with(',') {
+        out.pop();
+        push_once(repairs, Repair::RemovedTrailingComma);
+        truncate_whitespace(out);
+    }
+
+    out.push(frame.closer);
+    mark_value(stack);
+}
+
+/// 출력 끝이 잘린 리터럴이면 완성합니다. 완성할 수 없으면 false
+fn complete_literal(out: &mut String, repairs: &mut Vec<Repair>) -> bool {
+    let token_len = out.chars().rev()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-'))
+        .count();
+    if token_len == 0 { return true; }
+
+    let token = out[out.len() - token_len..].to_string();
+    if token.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        for literal in ["true", "false", "null"] {
+            if literal.starts_with(&token) {
+                if literal != token {
+                    out.push_str(&literal[token.len()..]);
+                    push_once(repairs, Repair::CompletedLiteral);
+                }
+                return true;
+            }
+        }
+        // 따옴표 없는 단어 등은 그대로 둠 (파싱 단계에서 오류로 보고됨)
+        return true;
+    }
+
+    // 숫자: "1.", "1e", "-" 처럼 끝난 경우
+    let trimmed = token.trim_end_matches(['.', 'e', 'E', '+', '-']);
+    if trimmed.len() != token.len() {
+        out.truncate(out.len() - (token.len() - trimmed.len()));
+        push_once(repairs, Repair::CompletedLiteral);
+    }
+    !trimmed.is_empty()
+}
+
+fn truncate_whitespace(out: &mut String) {
+    let len = out.trim_end().len();
+    out.truncate(len);
+}
+
+/// 문자열이 "\u12" 처럼 유니코드 이스케이프 중간에서 끝났으면 제거
+fn strip_partial_unicode_escape(out: &mut String) {
+    if let Some(pos) = out.rfind("\\u") {
+        let tail = &out[pos + 2..];
+        if tail.len() < 4 && tail.chars().all(|c| c.is_ascii_hexdigit()) {
+            out.truncate(pos);
+        }
+    }
+}
+
+fn push_escaped_control(out: &mut String, c: char) {
+    match c {
+        '\n' => out.push_str("\\n"),
+        '\r' => out.push_str("\\r"),
+        '\t' => out.push_str("\\t"),
+        _ => out.push_str(&format!("\\u{:04x}", c as u32)),
+    }
+}
+
+fn push_once(repairs: &mut Vec<Repair>, repair: Repair) {
+    if !repairs.contains(&repair) {
+        repairs.push(repair);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{repair_json, Repair};
+    use serde_json::{json, Value};
+
+    fn repaired(input: &str) -> (Value, Vec<Repair>) {
+        let r = repair_json(input);
+        let value = serde_json::from_str(&r.json).unwrap_or_else(|e| panic!("{}: {}", e, r.json));
+        (value, r.repairs)
+    }
+
+    #[test]
+    fn leaves_valid_json_untouched() {
+        let (value, repairs) = repaired(r#"{"a": [1, "}{]"], "b": null}"#);
+        assert_eq!(value, json!({"a": [1, "}{]"], "b": null}));
+        assert!(repairs.is_empty());
+    }
+
+    #[test]
+    fn removes_trailing_commas() {
+        let (value, repairs) = repaired(r#"{"a": [1, 2, ], "b": {"c": true,},}"#);
+        assert_eq!(value, json!({"a": [1, 2], "b": {"c": true}}));
+        assert_eq!(repairs, vec![Repair::RemovedTrailingComma]);
+    }
+
+    #[test]
+    fn quotes_bare_keys() {
+        let (value, repairs) = repaired(r#"{name: "삼성전자", entity_type: "Organization", meta: {page-no: 3}}"#);
+        assert_eq!(value, json!({"name": "삼성전자", "entity_type": "Organization", "meta": {"page-no": 3}}));
+        assert_eq!(repairs, vec![Repair::QuotedKey]);
+    }
+
+    #[test]
+    fn strips_markdown_fence_and_surrounding_text() {
+        let input = "분석 결과입니다.\n```json\n{\"topic\": \"회의록\", \"facts\": []}\n```\n도움이 되었길 바랍니다.";
+        let (value, repairs) = repaired(input);
+        assert_eq!(value, json!({"topic": "회의록", "facts": []}));
+        assert_eq!(repairs, vec![Repair::StrippedText]);
+    }
+
+    #[test]
+    fn closes_truncated_arrays() {
+        let (value, repairs) = repaired(r#"{"facts": ["매출 증가", "신규 채용"#);
+        assert_eq!(value, json!({"facts": ["매출 증가", "신규 채용"]}));
+        assert_eq!(repairs, vec![Repair::ClosedString, Repair::ClosedStructure]);
+
+        let (value, _) = repaired("[1, 2, 3.");
+        assert_eq!(value, json!([1, 2, 3]));
+
+        let (value, repairs) = repaired(r#"{"items": [{"name": "A"}, {"name": "B", "type":"#);
+        assert_eq!(value, json!({"items": [{"name": "A"}, {"name": "B"}]}));
+        assert!(repairs.contains(&Repair::DroppedIncompleteMember));
+
+        let (value, _) = repaired(r#"{"ok": tr"#);
+        assert_eq!(value, json!({"ok": true}));
+    }
+
+    #[test]
+    fn converts_single_quoted_strings() {
+        let (value, repairs) = repaired(r#"{'name': 'Apple\'s "Vision"', 'tags': ['a', "b"]}"#);
+        assert_eq!(value, json!({"name": "Apple's \"Vision\"", "tags": ["a", "b"]}));
+        assert_eq!(repairs, vec![Repair::ConvertedSingleQuotes]);
+    }
+
+    #[test]
+    fn escapes_control_chars_inside_strings() {
+        let (value, repairs) = repaired("{\"summary\": \"첫 줄\n둘째 줄\"}");
+        assert_eq!(value, json!({"summary": "첫 줄\n둘째 줄"}));
+        assert_eq!(repairs, vec![Repair::EscapedControlChar]);
+    }
+
+    #[test]
+    fn drops_stray_closers() {
+        let (value, repairs) = repaired(r#"{"a": [1]], "b": 2}"#);
+        assert_eq!(value, json!({"a": [1], "b": 2}));
+        assert_eq!(repairs, vec![Repair::RemovedStrayCloser]);
+    }
+}
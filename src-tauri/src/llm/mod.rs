@@ -1,3 +1,19 @@
 // src/llm/mod.rs
 
-pub mod extractor;
\ No newline at end of file
+pub mod extractor;
+pub mod embedder;
+pub mod chat;
+pub mod rewrite;
+pub mod budget;
+pub mod structured;
+pub mod json_repair;
+pub mod relations;
+
+/// OpenAI 호환 채팅 서버 주소 + 요청에 넣을 모델명
+#[derive(Debug, Clone)]
+pub struct LlmEndpoint {
+    pub base_url: String,
+    pub model: String,
+    /// 요청 하나가 쓸 수 있는 컨텍스트 크기 (llama-server는 --ctx-size를 --parallel 슬롯 수로 나눔)
+    pub ctx_size: usize,
+}
@@ -0,0 +1,53 @@
+// src/llm/relations.rs
+
+use crate::models::{RelationExtraction, RelationTriple};
+use super::budget::{PromptBudget, Tokenizer};
+use super::chat::ChatMessage;
+use super::structured::{extract_structured, ExtractionError, RetryPolicy};
+use super::LlmEndpoint;
+
+/// JSON 응답 생성용으로 비워둘 토큰 수
+const RELATION_OUTPUT_TOKENS: usize = 768;
+
+const RELATION_INSTRUCTION: &str = r#"
+You extract relations between entities for a Knowledge Graph.
+Read the text and list (subject, predicate, object) triples that the text explicitly states.
+
+### RULES ###
+1. Output MUST be valid JSON: { "relations": [ { "subject", "predicate", "object", "confidence" } ] }
+2. Use the entity names exactly as written. Prefer names from the [Known entities] list when they match.
+3. 'predicate' is a short label (1-3 words) in the language of the text.
+4. 'confidence' is between 0.0 and 1.0. Do not guess relations that are not in the text.
+5. If there are no relations, return { "relations": [] }.
+"#;
+
+/// Chunk 텍스트에서 (주어, 관계, 목적어) 트리플을 추출합니다.
+/// `known_entities`: 분석 단계(key_entities)에서 찾은 이름 (이름을 일관되게 쓰도록 힌트로 제공)
+pub async fn extract_relations(
+    endpoint: &LlmEndpoint,
+    policy: &RetryPolicy,
+    text: &str,
+    known_entities: &[String],
+) -> Result<Vec<RelationTriple>, ExtractionError> {
+    let instruction = RELATION_INSTRUCTION.trim();
+    let entities = if known_entities.is_empty() { "(none)".to_string() } else { known_entities.join(", ") };
+    let header = format!("[Known entities]\n{}\n\n[Text]\n", entities);
+
+    let tokenizer = Tokenizer::for_endpoint(endpoint);
+    let mut budget = PromptBudget::new(&tokenizer, endpoint.ctx_size, RELATION_OUTPUT_TOKENS);
+    budget.add_message(instruction).await;
+    budget.add_message(&header).await;
+    let truncated_text = budget.fit("relation input", text).await;
+    budget.finish().log();
+    let truncated_text = truncated_text.ok_or_else(|| ExtractionError::PromptTooLong {
+        message: "no room left for the relation input".to_string(),
+    })?;
+
+    let messages = [
+        ChatMessage::system(instruction),
+        ChatMessage::user(format!("{}{}", header, truncated_text)),
+    ];
+
+    let result = extract_structured::<RelationExtraction>(endpoint, "relations", &messages, 0.1, policy).await?;
+    Ok(result.relations)
+}
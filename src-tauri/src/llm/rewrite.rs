@@ -0,0 +1,49 @@
+// src/llm/rewrite.rs
+
+use std::error::Error;
+
+use super::chat::{complete_chat, ChatMessage};
+use super::LlmEndpoint;
+
+const REWRITE_INSTRUCTION: &str = r#"
+You rewrite the user's latest message into a standalone search query for a document search engine.
+
+### RULES ###
+1. Resolve pronouns and references ("it", "that company", "그거", "이 회사") using the conversation.
+2. Keep names, product codes and acronyms exactly as written.
+3. Keep the language of the latest message.
+4. Output ONLY the rewritten query on one line. No explanation, no quotes.
+"#;
+
+/// 이전 대화 맥락을 반영해 마지막 질문을 독립적인 검색 질의로 다시 씁니다.
+/// (예: "그 회사 매출은?" -> "삼성전자 매출")
+pub async fn rewrite_query(
+    endpoint: &LlmEndpoint,
+    history: &[ChatMessage],
+    question: &str,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let transcript = history.iter()
+        .map(|m| format!("{}: {}", m.role, m.content.chars().take(500).collect::<String>()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let messages = vec![
+        ChatMessage::system(REWRITE_INSTRUCTION.trim()),
+        ChatMessage::user(format!(
+            "### Conversation ###\n{}\n\n### Latest message ###\n{}\n\n### Standalone query ###",
+            transcript, question
+        )),
+    ];
+
+    let raw = complete_chat(endpoint, &messages, 0.0, 64).await?;
+    let rewritten = raw.lines()
+        .map(|l| l.trim().trim_matches('"'))
+        .find(|l| !l.is_empty())
+        .unwrap_or("")
+        .to_string();
+
+    if rewritten.is_empty() {
+        return Err("Empty rewrite".into());
+    }
+    Ok(rewritten)
+}
@@ -0,0 +1,241 @@
+// src/llm/structured.rs
+
+use reqwest::Client;
+use schemars::generate::SchemaSettings;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+use std::time::Duration;
+
+use super::chat::ChatMessage;
+use super::json_repair::repair_json;
+use super::LlmEndpoint;
+
+/// 구조화 추출 실패 원인
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExtractionError {
+    /// 서버 연결 실패 또는 HTTP 오류
+    /// `retryable`: 연결 실패, 타임아웃, 429, 5xx처럼 다시 보내면 성공할 수 있는 오류인지 (400/404 등은 false)
+    Request { message: String, retryable: bool },
+    /// 응답에 내용이 없음
+    EmptyResponse,
+    /// JSON으로 파싱할 수 없음
+    InvalidJson { message: String, raw: String },
+    /// JSON이지만 스키마와 맞지 않음 (필드 누락, 타입 불일치 등)
+    SchemaViolation { errors: Vec<String>, raw: String },
+    /// 지시문만으로 ctx-size가 차서 입력을 넣을 자리가 없음 (요청하지 않음)
+    PromptTooLong { message: String },
+    /// 스키마 자체가 잘못됨 (코드 문제이므로 모델에게 다시 요청하지 않음)
+    InvalidSchema { message: String },
+}
+
+impl fmt::Display for ExtractionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Request { message, .. } => write!(f, "LLM request failed: {}", message),
+            Self::EmptyResponse => write!(f, "LLM returned an empty response"),
+            Self::InvalidJson { message, .. } => write!(f, "LLM returned invalid JSON: {}", message),
+            Self::SchemaViolation { errors, .. } => write!(f, "LLM output does not match schema: {}", errors.join("; ")),
+            Self::PromptTooLong { message } => write!(f, "Prompt does not fit in the context window: {}", message),
+            Self::InvalidSchema { message } => write!(f, "Invalid JSON schema: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ExtractionError {}
+
+impl ExtractionError {
+    /// 같은 요청을 다시 보내면 성공할 수 있는 오류 (서버 기동 중, 일시적 과부하 등)
+    fn is_transient(&self) -> bool {
+        matches!(self, Self::Request { retryable: true, .. } | Self::EmptyResponse)
+    }
+
+    /// 모델에게 출력을 고치라고 다시 요청할 수 있는 오류
+    fn repair_hint(&self) -> Option<(String, String)> {
+        match self {
+            Self::InvalidJson { message, raw } => Some((raw.clone(), format!("The JSON could not be parsed: {}", message))),
+            Self::SchemaViolation { errors, raw } => Some((raw.clone(), format!("The JSON does not match the schema: {}", errors.join("; ")))),
+            _ => None,
+        }
+    }
+}
+
+/// 구조화 추출 재시도 정책
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// HTTP 오류 시 최대 요청 횟수 (첫 요청 포함)
+    pub max_attempts: u32,
+    /// 첫 재시도 전 대기 시간, 이후 2배씩 증가
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// 파싱/스키마 오류를 모델에게 알려주고 다시 받는 횟수
+    pub repair_attempts: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_attempts: 3, initial_backoff_ms: 500, max_backoff_ms: 8000, repair_attempts: 1 }
+    }
+}
+
+impl RetryPolicy {
+    fn backoff(&self, retry: u32) -> Duration {
+        let ms = self.initial_backoff_ms.saturating_mul(1u64 << retry.min(16));
+        Duration::from_millis(ms.min(self.max_backoff_ms))
+    }
+}
+
+impl From<reqwest::Error> for ExtractionError {
+    fn from(e: reqwest::Error) -> Self {
+        let retryable = e.is_connect() || e.is_timeout() || e.status().is_some_and(is_retryable_status);
+        Self::Request { message: e.to_string(), retryable }
+    }
+}
+
+/// 서버가 바쁘거나(429) 서버 쪽 오류(5xx)인 경우만 재시도 (요청 자체가 잘못된 4xx는 재시도해도 같음)
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// `T`의 JSON 스키마 (llama.cpp 문법 변환이 $ref를 거치지 않도록 하위 스키마를 인라인)
+pub fn schema_of<T: JsonSchema>() -> Value {
+    let schema = SchemaSettings::draft2020_12()
+        .with(|s| s.inline_subschemas = true)
+        .into_generator()
+        .into_root_schema_for::<T>();
+    schema.to_value()
+}
+
+/// 스키마를 `response_format: json_schema`로 보내 llama-server가 문법(grammar)으로 출력을 제한하게 하고,
+/// 응답을 같은 스키마로 검증한 뒤 `T`로 변환합니다.
+/// HTTP 오류는 지수 백오프로 재요청하고, 파싱/스키마 오류는 오류 내용을 모델에게 돌려주어 다시 받습니다.
+pub async fn extract_structured<T: JsonSchema + DeserializeOwned>(
+    endpoint: &LlmEndpoint,
+    name: &str,
+    messages: &[ChatMessage],
+    temperature: f32,
+    policy: &RetryPolicy,
+) -> Result<T, ExtractionError> {
+    extract_with_schema(endpoint, name, schema_of::<T>(), messages, temperature, policy).await
+}
+
+/// `extract_structured`와 같지만, 값 목록(enum) 등을 덧붙여 수정한 스키마를 사용합니다.
+pub async fn extract_with_schema<T: DeserializeOwned>(
+    endpoint: &LlmEndpoint,
+    name: &str,
+    schema: Value,
+    messages: &[ChatMessage],
+    temperature: f32,
+    policy: &RetryPolicy,
+) -> Result<T, ExtractionError> {
+    let mut messages = messages.to_vec();
+    let mut repairs = 0;
+
+    loop {
+        let content = request_with_backoff(endpoint, name, &messages, temperature, &schema, policy).await?;
+        let err = match parse_and_validate(&content, &schema) {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+
+        let Some((raw, hint)) = err.repair_hint() else { return Err(err) };
+        if repairs >= policy.repair_attempts { return Err(err); }
+        repairs += 1;
+
+        println!("    🔧 Repair re-prompt ({}/{}): {}", repairs, policy.repair_attempts, err);
+        messages.push(ChatMessage::assistant(raw));
+        messages.push(ChatMessage::user(format!(
+            "{}\nReturn the corrected JSON only, following the schema exactly.",
+            hint
+        )));
+    }
+}
+
+async fn request_with_backoff(
+    endpoint: &LlmEndpoint,
+    name: &str,
+    messages: &[ChatMessage],
+    temperature: f32,
+    schema: &Value,
+    policy: &RetryPolicy,
+) -> Result<String, ExtractionError> {
+    let mut attempt = 1;
+    loop {
+        match request_structured(endpoint, name, messages, temperature, schema).await {
+            Err(e) if e.is_transient() && attempt < policy.max_attempts => {
+                let wait = policy.backoff(attempt - 1);
+                println!("    ⏳ LLM request failed ({}), retrying in {:?} ({}/{})", e, wait, attempt, policy.max_attempts);
+                tokio::time::sleep(wait).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+async fn request_structured(
+    endpoint: &LlmEndpoint,
+    name: &str,
+    messages: &[ChatMessage],
+    temperature: f32,
+    schema: &Value,
+) -> Result<String, ExtractionError> {
+    let payload = json!({
+        "model": endpoint.model,
+        "messages": messages,
+        "temperature": temperature,
+        "response_format": {
+            "type": "json_schema",
+            "json_schema": { "name": name, "schema": schema, "strict": true }
+        }
+    });
+
+    let url = format!("{}/chat/completions", endpoint.base_url.trim_end_matches('/'));
+    let res = Client::new().post(&url).json(&payload).send().await?;
+    if !res.status().is_success() {
+        return Err(ExtractionError::Request {
+            message: format!("LLM Request Failed: {}", res.status()),
+            retryable: is_retryable_status(res.status()),
+        });
+    }
+
+    let resp_json: Value = res.json().await?;
+    let content = resp_json["choices"][0]["message"]["content"].as_str().unwrap_or("").trim();
+    if content.is_empty() {
+        return Err(ExtractionError::EmptyResponse);
+    }
+
+    Ok(content.to_string())
+}
+
+/// 응답 문자열 -> JSON -> 스키마 검증 -> `T`
+pub fn parse_and_validate<T: DeserializeOwned>(content: &str, schema: &Value) -> Result<T, ExtractionError> {
+    // JSON 수리 (문법 제한이 없는 서버이거나 출력이 잘렸을 때를 대비)
+    let repaired = repair_json(content);
+    if !repaired.repairs.is_empty() {
+        println!("    🩹 JSON repaired: {:?}", repaired.repairs);
+    }
+    let value: Value = serde_json::from_str(&repaired.json).map_err(|e| ExtractionError::InvalidJson {
+        message: e.to_string(),
+        raw: content.to_string(),
+    })?;
+
+    let validator = jsonschema::validator_for(schema).map_err(|e| ExtractionError::InvalidSchema {
+        message: e.to_string(),
+    })?;
+    let errors: Vec<String> = validator.iter_errors(&value)
+        .map(|e| format!("{}: {}", e.instance_path, e))
+        .collect();
+    if !errors.is_empty() {
+        return Err(ExtractionError::SchemaViolation { errors, raw: content.to_string() });
+    }
+
+    serde_json::from_value(value).map_err(|e| ExtractionError::SchemaViolation {
+        errors: vec![e.to_string()],
+        raw: content.to_string(),
+    })
+}
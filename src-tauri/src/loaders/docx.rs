@@ -0,0 +1,85 @@
+// src/loaders/docx.rs
+
+use anyhow::Context;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use super::{numbered, DocumentFormat, DocumentLoader, Section};
+
+pub struct DocxLoader;
+
+impl DocumentLoader for DocxLoader {
+    fn format(&self) -> DocumentFormat { DocumentFormat::Docx }
+
+    fn extensions(&self) -> &'static [&'static str] { &["docx"] }
+
+    /// 본문(word/document.xml)의 문단을 읽고, 제목 스타일 문단마다 새 섹션을 시작합니다.
+    fn load(&self, path: &Path) -> anyhow::Result<Vec<Section>> {
+        let mut archive = zip::ZipArchive::new(File::open(path)?)
+            .with_context(|| format!("Not a DOCX (zip) file: {:?}", path))?;
+        let mut xml = String::new();
+        archive.by_name("word/document.xml")
+            .context("word/document.xml not found")?
+            .read_to_string(&mut xml)?;
+
+        let mut sections: Vec<(Option<String>, String)> = vec![(None, String::new())];
+        for (text, is_heading) in paragraphs(&xml)? {
+            if is_heading && !text.trim().is_empty() {
+                sections.push((Some(text.trim().to_string()), String::new()));
+            }
+            let body = &mut sections.last_mut().expect("at least one section").1;
+            body.push_str(&text);
+            body.push_str("\n\n");
+        }
+        Ok(numbered(sections))
+    }
+}
+
+/// (문단 텍스트, 제목 여부) 목록
+/// 제목: 스타일 ID가 Heading*/Title 이거나 개요 수준(w:outlineLvl)이 지정된 문단
+fn paragraphs(xml: &str) -> anyhow::Result<Vec<(String, bool)>> {
+    let mut reader = Reader::from_str(xml);
+    let mut out = Vec::new();
+    let mut text = String::new();
+    let mut is_heading = false;
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"p" => { text.clear(); is_heading = false; }
+                b"t" => in_text = true,
+                _ => {}
+            },
+            Event::Empty(e) => match e.local_name().as_ref() {
+                b"pStyle" => {
+                    let style = attr(&e, b"val").unwrap_or_default().to_lowercase();
+                    if style.starts_with("heading") || style == "title" { is_heading = true; }
+                }
+                b"outlineLvl" => is_heading = true,
+                b"tab" => text.push('\t'),
+                b"br" | b"cr" => text.push('\n'),
+                _ => {}
+            },
+            Event::Text(t) if in_text => text.push_str(&t.unescape()?),
+            Event::End(e) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"p" => out.push((std::mem::take(&mut text), is_heading)),
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(out)
+}
+
+/// 네임스페이스 접두사를 무시하고 속성 값을 찾습니다. (w:val -> val)
+pub(crate) fn attr(e: &BytesStart, name: &[u8]) -> Option<String> {
+    e.attributes().flatten()
+        .find(|a| a.key.local_name().as_ref() == name)
+        .and_then(|a| a.unescape_value().ok().map(|v| v.to_string()))
+}
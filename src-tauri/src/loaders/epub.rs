@@ -0,0 +1,131 @@
+// src/loaders/epub.rs
+
+use anyhow::Context;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::Path;
+use zip::ZipArchive;
+
+use super::docx::attr;
+use super::html::html_to_text;
+use super::{numbered, DocumentFormat, DocumentLoader, Section};
+
+pub struct EpubLoader;
+
+impl DocumentLoader for EpubLoader {
+    fn format(&self) -> DocumentFormat { DocumentFormat::Epub }
+
+    fn extensions(&self) -> &'static [&'static str] { &["epub"] }
+
+    /// spine(읽는 순서)의 XHTML 문서 하나 = 섹션 하나 (제목은 문서의 첫 제목)
+    fn load(&self, path: &Path) -> anyhow::Result<Vec<Section>> {
+        let mut archive = ZipArchive::new(File::open(path)?)
+            .with_context(|| format!("Not an EPUB (zip) file: {:?}", path))?;
+
+        // META-INF/container.xml -> OPF 경로
+        let container = read_entry(&mut archive, "META-INF/container.xml")?;
+        let opf_path = find_attr(&container, b"rootfile", b"full-path")?
+            .context("rootfile not found in container.xml")?;
+        let opf_dir = opf_path.rsplit_once('/').map(|(dir, _)| format!("{}/", dir)).unwrap_or_default();
+
+        let opf = read_entry(&mut archive, &opf_path)?;
+        let spine = spine_hrefs(&opf)?;
+
+        let mut chapters = Vec::new();
+        for href in spine {
+            let entry = format!("{}{}", opf_dir, percent_decode(&href));
+            let html = match read_entry(&mut archive, &entry) {
+                Ok(h) => h,
+                Err(e) => {
+                    println!("    ⚠️ EPUB chapter {} skipped: {}", entry, e);
+                    continue;
+                }
+            };
+            let text = html_to_text(html.as_bytes())?;
+            let title = text.lines()
+                .map(str::trim)
+                .find(|l| l.starts_with('#'))
+                .map(|l| l.trim_start_matches('#').trim().to_string())
+                .filter(|t| !t.is_empty());
+            chapters.push((title, text));
+        }
+        Ok(numbered(chapters))
+    }
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> anyhow::Result<String> {
+    let mut s = String::new();
+    archive.by_name(name)
+        .with_context(|| format!("{} not found", name))?
+        .read_to_string(&mut s)?;
+    Ok(s)
+}
+
+/// 처음 나오는 `tag` 요소의 `attr_name` 속성
+fn find_attr(xml: &str, tag: &[u8], attr_name: &[u8]) -> anyhow::Result<Option<String>> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == tag => {
+                return Ok(attr(&e, attr_name));
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// OPF의 spine 순서대로 manifest href 목록 (XHTML/HTML 문서만)
+fn spine_hrefs(opf: &str) -> anyhow::Result<Vec<String>> {
+    let mut reader = Reader::from_str(opf);
+    // manifest id -> (href, media-type)
+    let mut manifest: HashMap<String, (String, String)> = HashMap::new();
+    let mut spine: Vec<String> = Vec::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"item" => {
+                    if let (Some(id), Some(href)) = (attr(&e, b"id"), attr(&e, b"href")) {
+                        manifest.insert(id, (href, attr(&e, b"media-type").unwrap_or_default()));
+                    }
+                }
+                b"itemref" => {
+                    if let Some(idref) = attr(&e, b"idref") { spine.push(idref); }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(spine.iter()
+        .filter_map(|id| manifest.get(id))
+        .filter(|(_, media)| media.contains("html"))
+        .map(|(href, _)| href.clone())
+        .collect())
+}
+
+/// href의 %XX 인코딩 해제 ("Chapter%201.xhtml" -> "Chapter 1.xhtml")
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(b) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
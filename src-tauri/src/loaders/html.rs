@@ -0,0 +1,28 @@
+// src/loaders/html.rs
+
+use std::path::Path;
+
+use super::markdown::split_sections;
+use super::{DocumentFormat, DocumentLoader, Section};
+
+/// html2text 출력 줄 너비 (문단이 임의로 줄바꿈되지 않도록 넉넉하게)
+const RENDER_WIDTH: usize = 10_000;
+
+pub struct HtmlLoader;
+
+impl DocumentLoader for HtmlLoader {
+    fn format(&self) -> DocumentFormat { DocumentFormat::Html }
+
+    fn extensions(&self) -> &'static [&'static str] { &["html", "htm", "xhtml"] }
+
+    fn load(&self, path: &Path) -> anyhow::Result<Vec<Section>> {
+        let bytes = std::fs::read(path)?;
+        let text = html_to_text(&bytes)?;
+        Ok(split_sections(&text))
+    }
+}
+
+/// HTML -> 텍스트. 제목(h1~h6)은 "# 제목" 형태로 남으므로 Markdown과 같은 방식으로 섹션을 나눌 수 있음
+pub(crate) fn html_to_text(html: &[u8]) -> anyhow::Result<String> {
+    Ok(html2text::from_read(html, RENDER_WIDTH)?)
+}
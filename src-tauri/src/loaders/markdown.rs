@@ -0,0 +1,71 @@
+// src/loaders/markdown.rs
+
+use std::path::Path;
+
+use super::text::read_text;
+use super::{numbered, DocumentFormat, DocumentLoader, Section};
+
+pub struct MarkdownLoader;
+
+impl DocumentLoader for MarkdownLoader {
+    fn format(&self) -> DocumentFormat { DocumentFormat::Markdown }
+
+    fn extensions(&self) -> &'static [&'static str] { &["md", "markdown"] }
+
+    fn load(&self, path: &Path) -> anyhow::Result<Vec<Section>> {
+        let text = read_text(path)?;
+        Ok(split_sections(strip_front_matter(&text)))
+    }
+}
+
+/// ATX 제목(# ~ ######)마다 새 섹션을 시작합니다. 코드 블록 안의 '#'은 무시하고,
+/// 섹션 본문에는 제목 줄도 포함합니다. (청크 분할의 Heading 전략이 다시 인식할 수 있도록)
+pub fn split_sections(text: &str) -> Vec<Section> {
+    let mut sections: Vec<(Option<String>, String)> = vec![(None, String::new())];
+    let mut fence: Option<&str> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) { fence = None; }
+        } else if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+        } else if let Some(title) = atx_heading(line) {
+            sections.push((Some(title), String::new()));
+        }
+
+        let body = &mut sections.last_mut().expect("at least one section").1;
+        body.push_str(line);
+        body.push('\n');
+    }
+
+    numbered(sections)
+}
+
+/// "## 제목 ##" -> "제목" (들여쓰기 3칸까지, '#' 뒤에는 공백이 있어야 함)
+fn atx_heading(line: &str) -> Option<String> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 { return None; }
+
+    let rest = &line[indent..];
+    let level = rest.chars().take_while(|&c| c == '#').count();
+    if level == 0 || level > 6 { return None; }
+
+    let after = &rest[level..];
+    if !after.is_empty() && !after.starts_with([' ', '\t']) { return None; }
+
+    let title = after.trim().trim_end_matches('#').trim();
+    if title.is_empty() { None } else { Some(title.to_string()) }
+}
+
+/// 맨 앞의 YAML front matter(--- ... ---) 제거
+fn strip_front_matter(text: &str) -> &str {
+    let Some(rest) = text.strip_prefix("---\n") else { return text };
+    match rest.find("\n---") {
+        Some(end) => {
+            let after = &rest[end + 4..];
+            after.strip_prefix('\n').unwrap_or(after)
+        }
+        None => text,
+    }
+}
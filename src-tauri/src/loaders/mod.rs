@@ -0,0 +1,66 @@
+// src/loaders/mod.rs
+
+pub mod pdf;
+pub mod text;
+pub mod markdown;
+pub mod html;
+pub mod docx;
+pub mod epub;
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 지원하는 문서 형식 (DocumentNode.metadata.format에 저장)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentFormat {
+    Pdf,
+    Markdown,
+    Text,
+    Html,
+    Docx,
+    Epub,
+}
+
+/// 문서의 논리적 구역 (PDF 페이지, Markdown/DOCX 제목 단위 섹션, EPUB 챕터 등)
+#[derive(Debug, Clone)]
+pub struct Section {
+    /// 문서 안에서의 위치 (1부터). PDF는 실제 페이지 번호, 그 외에는 섹션/챕터 순서
+    pub index: usize,
+    /// 섹션 제목 (제목이 없는 형식이면 None)
+    pub title: Option<String>,
+    pub text: String,
+}
+
+/// 파일 하나를 섹션 목록으로 읽는 로더 (형식마다 하나씩 구현)
+pub trait DocumentLoader: Send + Sync {
+    fn format(&self) -> DocumentFormat;
+    /// 처리할 확장자 (소문자, 점 없이)
+    fn extensions(&self) -> &'static [&'static str];
+    /// 내용이 없는 섹션은 반환하지 않습니다.
+    fn load(&self, path: &Path) -> anyhow::Result<Vec<Section>>;
+}
+
+const LOADERS: &[&dyn DocumentLoader] = &[
+    &pdf::PdfLoader,
+    &markdown::MarkdownLoader,
+    &text::TextLoader,
+    &html::HtmlLoader,
+    &docx::DocxLoader,
+    &epub::EpubLoader,
+];
+
+/// 확장자로 로더를 고릅니다. 지원하지 않는 형식이면 None
+pub fn loader_for(path: &Path) -> Option<&'static dyn DocumentLoader> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    LOADERS.iter().copied().find(|l| l.extensions().contains(&ext.as_str()))
+}
+
+/// 1부터 번호를 매기고 빈 섹션을 뺀 목록
+pub(crate) fn numbered(sections: impl IntoIterator<Item = (Option<String>, String)>) -> Vec<Section> {
+    sections.into_iter()
+        .enumerate()
+        .map(|(i, (title, text))| Section { index: i + 1, title, text })
+        .filter(|s| !s.text.trim().is_empty())
+        .collect()
+}
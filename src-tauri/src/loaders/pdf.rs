@@ -0,0 +1,36 @@
+// src/loaders/pdf.rs
+
+use anyhow::Context;
+use lopdf::Document;
+use std::path::Path;
+
+use super::{DocumentFormat, DocumentLoader, Section};
+
+pub struct PdfLoader;
+
+impl DocumentLoader for PdfLoader {
+    fn format(&self) -> DocumentFormat { DocumentFormat::Pdf }
+
+    fn extensions(&self) -> &'static [&'static str] { &["pdf"] }
+
+    /// 페이지 = 섹션. 빈 페이지는 건너뛰지만 index는 실제 페이지 번호를 유지
+    fn load(&self, path: &Path) -> anyhow::Result<Vec<Section>> {
+        // PDF 로드 (lopdf crate 사용)
+        let doc = Document::load(path)
+            .with_context(|| format!("Failed to load PDF: {:?}", path))?;
+
+        // 페이지 번호를 가져와서 순서대로 정렬 (1페이지부터)
+        let mut page_numbers: Vec<u32> = doc.get_pages().keys().cloned().collect();
+        page_numbers.sort();
+
+        let mut sections = Vec::new();
+        for page_num in page_numbers {
+            // 실패 시 에러를 내지 않고 빈 문자열 처리하여 진행
+            let text = doc.extract_text(&[page_num]).unwrap_or_default();
+            if !text.trim().is_empty() {
+                sections.push(Section { index: page_num as usize, title: None, text });
+            }
+        }
+        Ok(sections)
+    }
+}
@@ -0,0 +1,26 @@
+// src/loaders/text.rs
+
+use std::path::Path;
+
+use super::{numbered, DocumentFormat, DocumentLoader, Section};
+
+pub struct TextLoader;
+
+impl DocumentLoader for TextLoader {
+    fn format(&self) -> DocumentFormat { DocumentFormat::Text }
+
+    fn extensions(&self) -> &'static [&'static str] { &["txt", "text"] }
+
+    /// 폼 피드(\x0c)로 나뉜 페이지가 있으면 페이지 단위, 없으면 파일 전체가 하나의 섹션
+    fn load(&self, path: &Path) -> anyhow::Result<Vec<Section>> {
+        let text = read_text(path)?;
+        Ok(numbered(text.split('\u{0C}').map(|page| (None, page.to_string()))))
+    }
+}
+
+/// UTF-8로 읽고 (잘못된 바이트는 U+FFFD로 대체) BOM과 CRLF를 정리
+pub(crate) fn read_text(path: &Path) -> anyhow::Result<String> {
+    let bytes = std::fs::read(path)?;
+    let text = String::from_utf8_lossy(&bytes);
+    Ok(text.trim_start_matches('\u{FEFF}').replace("\r\n", "\n"))
+}
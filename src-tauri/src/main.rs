@@ -2,33 +2,97 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod models;
+mod config;
 mod database;
 mod utils;
+mod chunker;
 mod llm;
+mod retrieval;
+mod resolution;
+mod keywords;
+mod ingest;
+mod loaders;
+mod watcher;
+mod jobs;
 mod commands;
 
 use tauri::{Manager, RunEvent, AppHandle, Emitter};
 use tauri_plugin_shell::ShellExt;
 use tauri_plugin_shell::process::{CommandChild, CommandEvent}; // CommandEvent 추가
 use std::env;
-use std::sync::{Arc, Mutex};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration; // 딜레이용
 use tokio::time::sleep;  // 비동기 딜레이
 use rig::providers::openai::Client as OpenAiClient;
 use surrealdb::engine::local::{Db, RocksDb};
 use surrealdb::Surreal;
 
+use crate::config::{AppConfig, ServerConfig};
+use crate::llm::LlmEndpoint;
+
 // AppState 구조체
 struct AppState {
     db: Surreal<Db>,
-    embed_client: OpenAiClient, // Port 8080
-    gen_client: OpenAiClient,   // Port 8081
+    embed_client: RwLock<OpenAiClient>, // 설정의 embed 서버 (기본 8080)
+    gen_client: RwLock<OpenAiClient>,   // 설정의 chat 서버 (기본 8081)
+    config: RwLock<AppConfig>,
+    config_error: RwLock<Option<String>>, // crisper.toml을 읽지 못했으면 원인 (고쳐 저장할 때까지 llama-server를 띄우지 않음)
+    config_dir: PathBuf,
     server_handles: Arc<Mutex<Vec<CommandChild>>>,
+    watcher: Mutex<Option<watcher::FolderWatcher>>, // 감시 폴더 자동 Ingest (setup에서 시작)
+    jobs: jobs::JobManager, // Ingest/Graph/임베딩 백그라운드 작업
+    entity_names: retrieval::graph::EntityNameCache, // search_graph 이름 매칭용 Entity 이름 목록
 }
 
-// ♻️ 서버 실행/재시작을 담당하는 핵심 함수
-async fn start_servers(app: &AppHandle, use_gpu: bool) {
+impl AppState {
+    /// 현재 설정의 복사본
+    fn settings(&self) -> AppConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    fn embed_client(&self) -> OpenAiClient {
+        self.embed_client.read().unwrap().clone()
+    }
+
+    /// 채팅 서버 주소 + 모델명 (직접 HTTP 호출용)
+    fn chat_endpoint(&self) -> LlmEndpoint {
+        let config = self.config.read().unwrap();
+        let chat = &config.servers.chat;
+        LlmEndpoint {
+            base_url: config.chat_url(),
+            model: config.llm.chat_model.clone(),
+            ctx_size: (chat.ctx_size / chat.parallel.max(1)) as usize,
+        }
+    }
+
+    fn rerank_url(&self) -> String {
+        self.config.read().unwrap().rerank_url()
+    }
+
+    /// 새 설정을 적용합니다. (포트가 바뀌어도 재시작 없이 클라이언트를 다시 만듦)
+    fn apply_settings(&self, config: AppConfig) {
+        *self.embed_client.write().unwrap() = build_client(&config.embed_url());
+        *self.gen_client.write().unwrap() = build_client(&config.chat_url());
+        *self.config.write().unwrap() = config;
+    }
+}
+
+fn build_client(base_url: &str) -> OpenAiClient {
+    OpenAiClient::builder().base_url(base_url).api_key("sk-no-key").build().unwrap()
+}
+
+// ♻️ 서버 실행/재시작을 담당하는 핵심 함수 (설정은 AppState.config에서 읽음)
+async fn start_servers(app: &AppHandle) {
     let state = app.state::<AppState>();
+    let settings = state.settings();
+    let use_gpu = settings.servers.use_gpu;
+
+    // 설정 파일이 잘못됐으면 기본값(개발 PC의 모델 경로)으로 서버를 띄우지 않음
+    if let Some(e) = state.config_error.read().unwrap().as_ref() {
+        eprintln!("❌ 설정 오류로 서버를 시작하지 않습니다: {}", e);
+        return;
+    }
     
     // 1. 기존 프로세스 죽이기 (Clean up)
     {
@@ -50,75 +114,91 @@ async fn start_servers(app: &AppHandle, use_gpu: bool) {
     paths.push(resource_path.clone());
     let new_path_env = env::join_paths(paths).unwrap();
 
-    // 🚨 모델 경로 (본인 경로로 확인 필수!)
-    let embed_model_path = "C:/eoraha/crisper_app/crisper-app/src-tauri/models/ggml-model-Q4_K_M.gguf";
-    let chat_model_path  = "C:/eoraha/crisper_app/crisper-app/src-tauri/models/qwen2.5-7b-instruct-q2_k.gguf";
+    println!("🚀 서버 시작 (GPU 모드: {})", use_gpu);
 
-    // 3. GPU 옵션 결정
-    // GPU 모드면 99레이어(전부), CPU 모드면 0레이어
-    let embed_gpu = if use_gpu { "99" } else { "0" };
-    let chat_gpu  = if use_gpu { "99" } else { "0" }; // 채팅은 VRAM 부족 방지로 10만
+    // 3. 서버별 실행 인자 (모델 경로/포트/컨텍스트 크기는 설정 파일에서)
+    let host = settings.servers.host.as_str();
+    let servers = [
+        ("embed", &settings.servers.embed, vec!["--embedding", "--pooling", "mean"]),
+        ("chat", &settings.servers.chat, vec!["--alias", settings.llm.chat_model.as_str()]),
+        // 리랭커: 검색 결과 재정렬용 (--reranking)
+        ("rerank", &settings.servers.rerank, vec!["--reranking", "--pooling", "rank"]),
+    ];
 
-    println!("🚀 서버 시작 (GPU 모드: {})", use_gpu);
+    for (name, server, extra_args) in servers {
+        if !server.enabled { continue; }
 
-    // 4. 임베딩 서버 (8080) 실행
-    let (mut rx1, child1) = app.shell().sidecar("llama-server").unwrap()
-        .current_dir(&resource_path)
-        .env("PATH", &new_path_env)
-        .args([
-            "--model", embed_model_path,
-            "--port", "8080", "--host", "127.0.0.1",
-            "--embedding", "--pooling", "mean",
-            "--ctx-size", "2048", "--batch-size", "2048", "--ubatch-size", "2048",
-            "--parallel", "1",
-            "--n-gpu-layers", embed_gpu // 👈 동적 할당
-        ])
-        .spawn().expect("8080 서버 실패");
-
-    state.server_handles.lock().unwrap().push(child1);
-
-    // 5. 채팅 서버 (8081) 실행
-    let (mut rx2, child2) = app.shell().sidecar("llama-server").unwrap()
-        .current_dir(&resource_path)
-        .env("PATH", &new_path_env)
-        .args([
-            "--model", chat_model_path,
-            "--alias", "gpt-3.5-turbo",
-            "--port", "8081", 
-            "--host", "127.0.0.1",
-            //"--api", "openai",bn  
-            "--ctx-size", "4096", "--batch-size", "2048", "--ubatch-size", "2048",
-            "--parallel", "2",
-            "--n-gpu-layers", chat_gpu // 👈 동적 할당
-        ])
-        .spawn().expect("8081 서버 실패");
-
-    tauri::async_runtime::spawn(async move {
-        while let Some(event) = rx2.recv().await {
-            if let CommandEvent::Stdout(line) = event {
-                let log = String::from_utf8_lossy(&line);
-                // 너무 많은 로그가 나오지 않게 중요한 정보만 필터링해서 출력
-                if log.contains("CUDA") || log.contains("offloading") || log.contains("listening") {
-                    //println!("[Chat-8081] {}", log.trim());
-                }
-            } else if let CommandEvent::Stderr(line) = event {
-                //eprintln!("[Chat-ERR] {}", String::from_utf8_lossy(&line).trim());
+        let mut args = server_args(host, server, use_gpu);
+        args.extend(extra_args.into_iter().map(String::from));
+
+        let spawned = app.shell().sidecar("llama-server").unwrap()
+            .current_dir(&resource_path)
+            .env("PATH", &new_path_env)
+            .args(args)
+            .spawn();
+
+        let (mut rx, child) = match spawned {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("❌ {} 서버({}) 실행 실패: {}", name, server.port, e);
+                continue;
             }
+        };
+
+        if name == "chat" {
+            tauri::async_runtime::spawn(async move {
+                while let Some(event) = rx.recv().await {
+                    if let CommandEvent::Stdout(line) = event {
+                        let log = String::from_utf8_lossy(&line);
+                        // 너무 많은 로그가 나오지 않게 중요한 정보만 필터링해서 출력
+                        if log.contains("CUDA") || log.contains("offloading") || log.contains("listening") {
+                            //println!("[Chat-8081] {}", log.trim());
+                        }
+                    } else if let CommandEvent::Stderr(_line) = event {
+                        //eprintln!("[Chat-ERR] {}", String::from_utf8_lossy(&line).trim());
+                    }
+                }
+            });
         }
-    });
 
-    state.server_handles.lock().unwrap().push(child2);
+        state.server_handles.lock().unwrap().push(child);
+    }
 
-    // (선택) 로그 모니터링은 여기서 간단히 처리하거나 생략 가능
-    // ...
     println!("🚀 적용 완료! (GPU 모드: {})", use_gpu);
 }
 
+/// llama-server 공통 실행 인자
+fn server_args(host: &str, server: &ServerConfig, use_gpu: bool) -> Vec<String> {
+    // GPU 모드면 설정된 레이어 수, CPU 모드면 0레이어
+    let gpu_layers = if use_gpu { server.gpu_layers } else { 0 };
+    vec![
+        "--model".into(), server.model_path.clone(),
+        "--port".into(), server.port.to_string(),
+        "--host".into(), host.to_string(),
+        "--ctx-size".into(), server.ctx_size.to_string(),
+        "--batch-size".into(), server.batch_size.to_string(),
+        "--ubatch-size".into(), server.batch_size.to_string(),
+        "--parallel".into(), server.parallel.to_string(),
+        "--n-gpu-layers".into(), gpu_layers.to_string(),
+    ]
+}
+
 // 🎛️ 프론트엔드에서 호출할 토글 커맨드
 #[tauri::command]
 async fn toggle_gpu(app: AppHandle, enable: bool) -> Result<String, String> {
     println!("🎛️ GPU 토글 요청: {}", enable);
-    start_servers(&app, enable).await;
+    {
+        let state = app.state::<AppState>();
+        // 잘못된 설정 파일을 기본값으로 덮어쓰지 않도록
+        if let Some(e) = state.config_error.read().unwrap().as_ref() {
+            return Err(format!("설정 파일을 먼저 고쳐주세요: {}", e));
+        }
+        let mut settings = state.settings();
+        settings.servers.use_gpu = enable;
+        settings.save(&state.config_dir).map_err(|e| e.to_string())?;
+        state.apply_settings(settings);
+    }
+    start_servers(&app).await;
     Ok(if enable { "GPU Mode ON" } else { "CPU Mode ON" }.to_string())
 }
 
@@ -131,16 +211,36 @@ async fn main() {
     }
     env_logger::init();
 
-    let db = database::init_db().await.expect("DB Init Failed");
-    let embed_client = OpenAiClient::builder().base_url("http://127.0.0.1:8080/v1").api_key("sk-no-key").build().unwrap();
-    let gen_client = OpenAiClient::builder().base_url("http://127.0.0.1:8081/v1").api_key("sk-no-key").build().unwrap();
+    // 설정 로드 (기본값 -> crisper.toml -> CRISPER_* 환경변수)
+    // 잘못된 설정이면 기본값으로 앱만 띄우고, 오류는 get_settings_error로 UI에 알림 (llama-server는 시작하지 않음)
+    let config_dir = config::config_dir();
+    let (settings, config_error) = match AppConfig::load(&config_dir) {
+        Ok(settings) => (settings, None),
+        Err(e) => {
+            eprintln!("❌ 설정 로드 실패: {}", e);
+            (AppConfig::default(), Some(e.to_string()))
+        }
+    };
+    println!("⚙️ Settings: {:?}", config_dir);
+
+    let db = database::init_db(&settings.database.path).await.expect("DB Init Failed");
+    let embed_client = build_client(&settings.embed_url());
+    let gen_client = build_client(&settings.chat_url());
     
     // 핸들 저장소 생성
     let server_handles = Arc::new(Mutex::new(Vec::new()));
 
     let app_state = AppState { 
-        db, embed_client, gen_client, 
-        server_handles: server_handles.clone() 
+        db,
+        embed_client: RwLock::new(embed_client),
+        gen_client: RwLock::new(gen_client),
+        config: RwLock::new(settings),
+        config_error: RwLock::new(config_error),
+        config_dir,
+        server_handles: server_handles.clone(),
+        watcher: Mutex::new(None),
+        jobs: jobs::JobManager::default(),
+        entity_names: retrieval::graph::EntityNameCache::default(),
     };
 
     let app = tauri::Builder::default()
@@ -151,15 +251,44 @@ async fn main() {
             crate::commands::ingest::ingest_documents,
             crate::commands::ingest::construct_graph,
             crate::commands::ingest::get_documents,
+            crate::commands::ingest::retry_failed_chunks,
+            crate::commands::relations::extract_chunk_relations,
+            crate::commands::resolution::find_merge_candidates,
+            crate::commands::resolution::auto_merge_entities,
+            crate::commands::resolution::merge_entities,
+            crate::commands::watch::add_watched_folder,
+            crate::commands::watch::remove_watched_folder,
+            crate::commands::watch::list_watched_folders,
+            crate::commands::embed::backfill_embeddings,
+            crate::commands::jobs::list_jobs,
+            crate::commands::jobs::get_job,
+            crate::commands::jobs::cancel_job,
+            crate::commands::jobs::pause_job,
+            crate::commands::jobs::resume_job,
+            crate::commands::search::search_docs,
+            crate::commands::search::search_graph,
+            crate::commands::chat::chat_with_docs,
+            crate::commands::conversation::create_conversation,
+            crate::commands::conversation::list_conversations,
+            crate::commands::conversation::rename_conversation,
+            crate::commands::conversation::delete_conversation,
+            crate::commands::conversation::get_conversation_messages,
             crate::commands::query::fetch_graph_data,
+            crate::commands::settings::get_settings,
+            crate::commands::settings::update_settings,
+            crate::commands::settings::get_settings_error,
             toggle_gpu, // 👈 커맨드 등록!
         ])
         .setup(move |app| {
-            // 앱 켜질 때는 기본적으로 CPU 모드(false)로 시작 (혹은 true로 설정 가능)
+            // GPU 사용 여부는 설정(servers.use_gpu)을 따름 (기본값 CPU)
             let handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
-                start_servers(&handle, false).await; 
+                start_servers(&handle).await; 
             });
+            // 감시 폴더 복원 + 파일 변경 시 자동 Ingest
+            tauri::async_runtime::spawn(watcher::run(app.handle().clone()));
+            // 작업자 시작 (앱 종료로 끊긴 작업은 다시 대기열로)
+            tauri::async_runtime::spawn(jobs::runner::start(app.handle().clone()));
             Ok(())
         })
         .build(tauri::generate_context!())
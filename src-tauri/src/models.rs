@@ -1,9 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use surrealdb::sql::Thing;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
-use serde_json::Value;
 
 // =======================
 // DB Nodes (SurrealDB)
@@ -23,22 +23,61 @@ pub struct DocumentNode {
     pub id: Option<Thing>,
     pub filename: String,
     pub created_at: DateTime<Utc>,
+    /// 파일 내용의 SHA-256 (같은 내용이면 다시 Ingest하지 않음)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    /// 원본 파일 경로 (내용이 바뀐 파일을 찾아 Chunk를 교체하는 기준)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_path: Option<String>,
+    /// Ingest 폴더 기준 상대 경로 ("/" 구분). 폴더 구조를 Folder 노드로 만들 때 사용
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_path: Option<String>,
     pub metadata: HashMap<String, JsonValue>,
 }
 
+/// 자동 Ingest 대상으로 등록한 폴더 (앱을 다시 켜도 계속 감시)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchedFolder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Thing>,
+    /// 절대 경로
+    pub path: String,
+    pub created_at: DateTime<Utc>,
+    /// 마지막으로 폴더 전체를 동기화한 시각
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ChunkNode {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<Thing>,
     pub content: String,
+    /// 청크가 시작하는 페이지 (0부터, 전체 범위는 metadata.page_start/page_end)
     pub page_index: usize,
+    /// 임베딩 서버(8080)에서 생성한 벡터 (아직 없으면 필드 자체를 저장하지 않음)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub embedding: Vec<f32>,
     
+    /// LLM 분석 상태 (failed면 retry_failed_chunks로 다시 분석)
+    #[serde(default)]
+    pub status: ChunkStatus,
+    
     /// 청크 분석 결과(CoreAnalysisResult 등)가 담기는 필드
     #[serde(default)] 
     pub metadata: HashMap<String, serde_json::Value>, 
 }
 
+/// Chunk의 LLM 분석 상태 (필드가 없는 기존 Chunk는 analyzed로 간주)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkStatus {
+    #[default]
+    Analyzed,
+    /// 재시도/수리 후에도 분석 실패 (metadata.analysis_error에 원인 기록)
+    Failed,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EntityNode {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -46,10 +85,41 @@ pub struct EntityNode {
     pub name: String,
     pub category: String,
     pub description: String,
+    /// 병합되었거나 다르게 표기된 이름 (entity resolution)
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub embedding: Vec<f32>,
     pub created_at: DateTime<Utc>,
 }
 
+/// 채팅 세션 (대화방)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConversationNode {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Thing>,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 대화 메시지 (user / assistant)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MessageNode {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Thing>,
+    pub conversation: Thing,
+    pub role: String,
+    pub content: String,
+    /// assistant 메시지가 참고한 출처 (message -> cited -> chunk Edge와 동일한 정보)
+    #[serde(default)]
+    pub citations: Vec<Citation>,
+    /// user 메시지를 검색용으로 재작성한 질의 (검색 디버깅용)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rewritten_query: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 // =======================
 // API Response DTOs
 // =======================
@@ -60,6 +130,12 @@ pub struct DocumentWithChunks {
     pub id: Thing,
     pub filename: String,
     pub created_at: chrono::DateTime<Utc>,
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    #[serde(default)]
+    pub source_path: Option<String>,
+    #[serde(default)]
+    pub relative_path: Option<String>,
     pub metadata: HashMap<String, serde_json::Value>,
     
     /// 서브쿼리를 통해 채워지는 청크 리스트
@@ -68,14 +144,113 @@ pub struct DocumentWithChunks {
 }
 
 /// LLM 분석 결과 (Step 1)
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// JsonSchema로 만든 스키마가 LLM 출력 형식 제한 + 응답 검증에 그대로 사용됩니다. (필드 주석 = 스키마 description)
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct CoreAnalysisResult {
-    /// 문서/청크의 주제
+    /// A short, descriptive title for this segment
     pub topic: String, 
-    /// 문맥 요약 (한국어)
+    /// Contextual summary in Korean (1-2 sentences)
     pub summary: String,
-    /// 그래프 생성을 위한 핵심 키워드/엔티티 리스트
-    pub key_entities: Vec<String>,
+    /// Important named things in the text that could become graph nodes
+    pub key_entities: Vec<KeyEntity>,
     /// 추가적인 상세 데이터 (Type, Facts 등)
-    pub detailed_data: Value, 
-}
\ No newline at end of file
+    pub detailed_data: DetailedData, 
+}
+
+/// 분석 결과의 핵심 Entity (construct_graph에서 EntityNode가 됨)
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct KeyEntity {
+    /// Entity name exactly as written in the text
+    pub name: String,
+    /// Entity type, one of the allowed categories
+    #[serde(rename = "type")]
+    pub entity_type: String,
+    /// One short sentence in Korean describing the entity in this text
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct DetailedData {
+    /// Text type (e.g. Code, Meeting, News, Paper)
+    #[serde(rename = "type")]
+    pub text_type: String,
+    /// Key facts in Korean
+    pub facts: Vec<String>,
+    /// Neutral / Positive / Negative
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sentiment: Option<String>,
+}
+
+/// LLM 관계 추출 결과 (entity -> related_to -> entity)
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct RelationExtraction {
+    /// Relations stated in the text. Empty if there are none.
+    pub relations: Vec<RelationTriple>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct RelationTriple {
+    /// Subject entity name, exactly as written in the text
+    pub subject: String,
+    /// Short relation label (e.g. "개발함", "소속", "uses", "located_in")
+    pub predicate: String,
+    /// Object entity name, exactly as written in the text
+    pub object: String,
+    /// How clearly the text states this relation, from 0.0 to 1.0
+    pub confidence: f32,
+}
+
+/// 검색(Retrieval) 결과로 반환되는 Chunk (점수 + 출처 정보 포함)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RetrievedChunk {
+    /// "chunk:⟨uuid⟩" 형태의 문자열 ID
+    pub id: String,
+    pub content: String,
+    /// 유사도 점수 (높을수록 관련성 높음)
+    pub score: f32,
+    pub document_id: Option<String>,
+    pub filename: String,
+    /// 1부터 시작하는 페이지 번호
+    pub page_number: usize,
+    /// 이 결과를 찾아낸 검색기 목록 ("vector", "bm25")
+    #[serde(default)]
+    pub retrievers: Vec<String>,
+}
+
+/// 답변에 사용된 문서 조각의 출처
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Citation {
+    /// 프롬프트에서 사용한 번호 ([1], [2], ...)
+    pub index: usize,
+    pub chunk_id: String,
+    pub document_id: Option<String>,
+    pub filename: String,
+    pub page_number: usize,
+    pub score: f32,
+    /// 답변 본문에 [index] 표기가 실제로 등장했는지 여부
+    pub cited: bool,
+}
+
+/// 대화 목록 조회용 (ID는 UUID 문자열)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConversationSummary {
+    pub id: String,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub message_count: usize,
+}
+
+/// 대화 이어하기(resume)용 메시지 (ID는 UUID 문자열)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MessageRecord {
+    pub id: String,
+    pub role: String,
+    pub content: String,
+    #[serde(default)]
+    pub citations: Vec<Citation>,
+    #[serde(default)]
+    pub rewritten_query: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,79 @@
+// src/resolution/merge.rs
+
+use surrealdb::engine::local::Db;
+use surrealdb::sql::Thing;
+use surrealdb::Surreal;
+
+use crate::models::EntityNode;
+
+/// 별칭(aliases)에 `name`이 등록된 Entity (병합되어 사라진 이름을 살아남은 Entity로 연결)
+pub async fn resolve_alias(db: &Surreal<Db>, name: &str) -> surrealdb::Result<Option<Thing>> {
+    let ids: Vec<Thing> = db
+        .query("SELECT VALUE id FROM entity WHERE aliases CONTAINS $name LIMIT 1")
+        .bind(("name", name.to_string()))
+        .await?.take(0)?;
+    Ok(ids.into_iter().next())
+}
+
+/// `merge` Entity를 `keep`에 합칩니다.
+/// mentions / related_to Edge를 `keep`으로 옮기고, 이름과 별칭을 `keep.aliases`에 더한 뒤 `merge`를 삭제합니다.
+pub async fn merge_into(db: &Surreal<Db>, keep: &Thing, merge: &Thing) -> anyhow::Result<()> {
+    if keep == merge { return Ok(()); }
+
+    let kept = select_entity(db, keep).await?;
+    let merged = select_entity(db, merge).await?;
+    let (Some(kept), Some(merged)) = (kept, merged) else {
+        anyhow::bail!("Entity not found: {} or {}", keep, merge);
+    };
+
+    // 별칭 합치기 + 비어 있는 분류/설명 보충
+    let mut aliases = kept.aliases.clone();
+    for alias in std::iter::once(merged.name.clone()).chain(merged.aliases.iter().cloned()) {
+        if alias != kept.name && !aliases.contains(&alias) {
+            aliases.push(alias);
+        }
+    }
+    let generic = |c: &str| c.is_empty() || c == "Keyword" || c == "Other";
+    let category = if generic(&kept.category) && !generic(&merged.category) { merged.category } else { kept.category };
+    let description = if kept.description.is_empty() { merged.description } else { kept.description };
+
+    // 중간에 실패하면 Edge가 반쯤 옮겨진 채 남지 않도록 한 트랜잭션으로 실행
+    // 1. mentions: 같은 Chunk가 이미 keep을 언급하고 있으면 옮기지 않음
+    // 2. related_to: merge 쪽 끝을 keep으로 바꿔 다시 연결 (keep과 merge 사이의 관계는 자기 자신을 가리키므로 버림)
+    //    관계명/신뢰도/근거/생성 시각은 원래 Edge에서 그대로 복사
+    // 3. 별칭/분류/설명 갱신
+    // 4. merge 쪽 Edge와 Entity 삭제
+    db.query("
+        BEGIN TRANSACTION;
+        FOR $c IN (SELECT VALUE in FROM mentions WHERE out = $merge AND in NOT IN (SELECT VALUE in FROM mentions WHERE out = $keep)) {
+            RELATE $c->mentions->$keep;
+        };
+        FOR $edge IN (SELECT * FROM related_to WHERE in = $merge OR out = $merge) {
+            LET $s = IF $edge.in = $merge { $keep } ELSE { $edge.in };
+            LET $o = IF $edge.out = $merge { $keep } ELSE { $edge.out };
+            IF $s != $o {
+                RELATE $s->related_to->$o SET relation = $edge.relation, confidence = $edge.confidence,
+                    evidence = $edge.evidence, created_at = $edge.created_at ?? time::now();
+            };
+        };
+        UPDATE $keep SET aliases = $aliases, category = $category, description = $description;
+        DELETE mentions WHERE out = $merge;
+        DELETE related_to WHERE in = $merge OR out = $merge;
+        DELETE $merge;
+        COMMIT TRANSACTION;
+    ")
+        .bind(("keep", keep.clone()))
+        .bind(("merge", merge.clone()))
+        .bind(("aliases", aliases))
+        .bind(("category", category))
+        .bind(("description", description))
+        .await?.check()?;
+
+    Ok(())
+}
+
+async fn select_entity(db: &Surreal<Db>, id: &Thing) -> surrealdb::Result<Option<EntityNode>> {
+    db.query("SELECT * FROM ONLY $id")
+        .bind(("id", id.clone()))
+        .await?.take(0)
+}
@@ -0,0 +1,310 @@
+// src/resolution/mod.rs
+
+pub mod similarity;
+pub mod merge;
+pub mod normalize;
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use surrealdb::engine::local::Db;
+use surrealdb::sql::Thing;
+use surrealdb::Surreal;
+
+use crate::database::ensure_vector_index;
+use similarity::{canonical_name, cosine, jaccard, name_similarity};
+
+/// 블로킹 키 길이 (비교용 이름의 앞 글자 수)
+/// 이름/별칭의 앞 글자가 같은 Entity끼리만 비교 (모든 쌍을 비교하면 Entity 수의 제곱)
+const BLOCK_KEY_CHARS: usize = 1;
+
+/// Entity 병합 후보 탐색 옵션
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ResolutionOptions {
+    /// 이 점수 이상인 쌍만 후보로 반환
+    pub min_score: f32,
+    /// auto_merge_entities에서 자동으로 합칠 최소 점수
+    pub auto_merge_threshold: f32,
+    /// 이름 유사도(정규화 + 편집 거리) 가중치
+    pub name_weight: f32,
+    /// 임베딩 코사인 유사도 가중치
+    pub embedding_weight: f32,
+    /// 함께 언급된 Entity 집합의 겹침(Jaccard) 가중치
+    pub context_weight: f32,
+    /// 반환할 최대 후보 수
+    pub max_candidates: usize,
+    /// Entity마다 임베딩이 가까운 이웃 몇 개를 추가로 비교할지
+    /// 표기가 달라 앞 글자가 다른 쌍("OpenAI"/"오픈AI")은 이름 블록에 함께 들어가지 않음
+    pub embedding_neighbours: usize,
+}
+
+impl Default for ResolutionOptions {
+    fn default() -> Self {
+        Self {
+            min_score: 0.75,
+            auto_merge_threshold: 0.9,
+            name_weight: 0.5,
+            embedding_weight: 0.35,
+            context_weight: 0.15,
+            max_candidates: 200,
+            embedding_neighbours: 5,
+        }
+    }
+}
+
+/// 신호별 점수 (해당 신호를 계산할 수 없으면 None)
+#[derive(Debug, Serialize, Clone)]
+pub struct MatchSignals {
+    pub name: f32,
+    pub embedding: Option<f32>,
+    pub context: Option<f32>,
+}
+
+impl MatchSignals {
+    /// 자동 병합할 근거가 있는지: 비교용 이름이 같거나, 이름 외의 신호(임베딩, 문맥)가 하나라도 있음
+    /// 이름 유사도만으로는 오타("Samsun")와 다른 대상("김민수"/"김민주")을 구분할 수 없음
+    pub fn supports_auto_merge(&self) -> bool {
+        self.name >= 1.0 || self.embedding.is_some() || self.context.is_some()
+    }
+}
+
+/// 같은 대상으로 보이는 Entity 쌍. `merge`를 `keep`에 합치는 것을 제안
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeCandidate {
+    pub keep_id: String,
+    pub keep_name: String,
+    pub merge_id: String,
+    pub merge_name: String,
+    pub score: f32,
+    pub signals: MatchSignals,
+}
+
+#[derive(Debug, Deserialize)]
+struct EntityRow {
+    id: Thing,
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    embedding: Vec<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MentionRow {
+    chunk: Thing,
+    entity: Thing,
+}
+
+/// 이름 앞 글자가 같거나 임베딩이 가까운 Entity 쌍을 비교해 병합 후보를 점수 내림차순으로 반환합니다.
+/// 점수 = 사용 가능한 신호(이름, 임베딩, 문맥)의 가중 평균. 비교용 이름이 같으면 1.0
+pub async fn find_candidates(db: &Surreal<Db>, opts: &ResolutionOptions) -> anyhow::Result<Vec<MergeCandidate>> {
+    let entities: Vec<EntityRow> = db
+        .query("SELECT id, name, aliases, embedding FROM entity")
+        .await?.take(0)?;
+    let mentions: Vec<MentionRow> = db
+        .query("SELECT in AS chunk, out AS entity FROM mentions")
+        .await?.take(0)?;
+
+    // Entity별 언급 수와, 같은 Chunk에서 함께 언급된 Entity 집합
+    let mut chunk_entities: HashMap<String, Vec<String>> = HashMap::new();
+    for m in &mentions {
+        chunk_entities.entry(m.chunk.to_string()).or_default().push(m.entity.to_string());
+    }
+    let mut mention_count: HashMap<String, usize> = HashMap::new();
+    let mut context: HashMap<String, HashSet<String>> = HashMap::new();
+    for members in chunk_entities.values() {
+        for e in members {
+            *mention_count.entry(e.clone()).or_default() += 1;
+            let neighbours = context.entry(e.clone()).or_default();
+            neighbours.extend(members.iter().filter(|o| *o != e).cloned());
+        }
+    }
+
+    let neighbours = embedding_neighbours(db, &entities, opts.embedding_neighbours).await?;
+    let pairs = candidate_pairs(&entities, &neighbours);
+
+    let empty = HashSet::new();
+    let mut candidates = Vec::new();
+    for (i, j) in pairs {
+        let (a, b) = (&entities[i], &entities[j]);
+        let (a_key, b_key) = (a.id.to_string(), b.id.to_string());
+
+        // 이름 + 별칭 조합 중 가장 비슷한 것
+        let name = a.names()
+            .flat_map(|x| b.names().map(move |y| name_similarity(x, y)))
+            .fold(0.0f32, f32::max);
+        let embedding = cosine(&a.embedding, &b.embedding).map(|s| s.max(0.0));
+
+        // 서로를 제외한 문맥으로 비교 (서로 함께 언급된 것만으로 점수가 오르지 않도록)
+        let ctx_a: HashSet<String> = context.get(&a_key).unwrap_or(&empty).iter().filter(|e| **e != b_key).cloned().collect();
+        let ctx_b: HashSet<String> = context.get(&b_key).unwrap_or(&empty).iter().filter(|e| **e != a_key).cloned().collect();
+        let ctx = jaccard(&ctx_a, &ctx_b);
+
+        let signals = MatchSignals { name, embedding, context: ctx };
+        let score = combine(&signals, opts);
+        if score < opts.min_score { continue; }
+
+        // 더 많이 언급된 쪽을 남김 (같으면 이름이 짧은 쪽)
+        let count = |key: &str| mention_count.get(key).copied().unwrap_or(0);
+        let a_first = (count(&a_key), std::cmp::Reverse(a.name.chars().count()))
+            >= (count(&b_key), std::cmp::Reverse(b.name.chars().count()));
+        let (keep, merge) = if a_first { (a, b) } else { (b, a) };
+
+        candidates.push(MergeCandidate {
+            keep_id: keep.id.to_string(),
+            keep_name: keep.name.clone(),
+            merge_id: merge.id.to_string(),
+            merge_name: merge.name.clone(),
+            score,
+            signals,
+        });
+    }
+
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates.truncate(opts.max_candidates);
+    Ok(candidates)
+}
+
+/// Entity마다 HNSW 인덱스로 임베딩이 가까운 이웃 `k`개를 찾아 인덱스 쌍으로 반환합니다.
+async fn embedding_neighbours(db: &Surreal<Db>, entities: &[EntityRow], k: usize) -> anyhow::Result<Vec<(usize, usize)>> {
+    let Some(dim) = entities.iter().map(|e| e.embedding.len()).find(|&d| d > 0) else { return Ok(vec![]) };
+    if k == 0 { return Ok(vec![]); }
+    ensure_vector_index(db, "entity", dim).await?;
+
+    let index: HashMap<String, usize> = entities.iter().enumerate().map(|(i, e)| (e.id.to_string(), i)).collect();
+    // 자기 자신이 가장 가까운 이웃으로 나오므로 하나 더 가져옴
+    let top_k = k + 1;
+    let ef = (top_k * 2).max(40);
+    let sql = format!("SELECT VALUE id FROM entity WHERE embedding <|{top_k},{ef}|> $vec");
+
+    let mut pairs = Vec::new();
+    for (i, entity) in entities.iter().enumerate() {
+        if entity.embedding.len() != dim { continue; }
+        let ids: Vec<Thing> = db.query(sql.as_str())
+            .bind(("vec", entity.embedding.clone()))
+            .await?.take(0)?;
+        pairs.extend(ids.iter().filter_map(|id| index.get(&id.to_string())).map(|&j| (i, j)));
+    }
+    Ok(pairs)
+}
+
+/// 비교할 Entity 쌍: 이름/별칭의 블로킹 키가 같은 쌍 + 임베딩 이웃 쌍 (중복/자기 자신 제외, 작은 인덱스가 앞)
+fn candidate_pairs(entities: &[EntityRow], neighbours: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    // 블로킹 키 -> Entity 인덱스 (별칭 때문에 한 Entity가 여러 블록에 들어갈 수 있음)
+    let mut blocks: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, entity) in entities.iter().enumerate() {
+        for key in entity.block_keys() {
+            blocks.entry(key).or_default().push(i);
+        }
+    }
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    for members in blocks.values() {
+        for (n, &i) in members.iter().enumerate() {
+            pairs.extend(members[n + 1..].iter().map(|&j| (i.min(j), i.max(j))));
+        }
+    }
+    pairs.extend(neighbours.iter().filter(|(i, j)| i != j).map(|&(i, j)| (i.min(j), i.max(j))));
+    pairs.sort_unstable();
+    pairs.dedup();
+    pairs
+}
+
+/// 후보 목록을 점수 순으로 병합합니다. 이미 다른 Entity에 합쳐진 쪽은 살아남은 Entity로 따라가 연결합니다.
+/// 반환: 병합한 쌍의 수
+pub async fn merge_candidates(db: &Surreal<Db>, candidates: &[MergeCandidate]) -> anyhow::Result<usize> {
+    // 합쳐진 Entity -> 흡수한 Entity
+    let mut merged_into: HashMap<String, String> = HashMap::new();
+    let resolve = |map: &HashMap<String, String>, id: &str| {
+        let mut id = id.to_string();
+        while let Some(next) = map.get(&id) { id = next.clone(); }
+        id
+    };
+
+    let mut count = 0;
+    for c in candidates {
+        let keep = resolve(&merged_into, &c.keep_id);
+        let merge = resolve(&merged_into, &c.merge_id);
+        if keep == merge { continue; }
+
+        let (keep_thing, merge_thing) = (parse_entity_id(&keep)?, parse_entity_id(&merge)?);
+        merge::merge_into(db, &keep_thing, &merge_thing).await?;
+        println!("    🔀 Merged {} -> {} ({:.2})", c.merge_name, c.keep_name, c.score);
+
+        merged_into.insert(merge, keep);
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// "entity:xxx" 형식의 ID를 Thing으로 변환
+pub fn parse_entity_id(id: &str) -> anyhow::Result<Thing> {
+    let thing: Thing = surrealdb::sql::thing(id).map_err(|e| anyhow::anyhow!("Invalid entity id {}: {}", id, e))?;
+    if thing.tb != "entity" {
+        anyhow::bail!("Not an entity id: {}", id);
+    }
+    Ok(thing)
+}
+
+/// 신호별 점수의 가중 평균 (계산할 수 없는 신호는 가중치에서 제외)
+fn combine(signals: &MatchSignals, opts: &ResolutionOptions) -> f32 {
+    if signals.name >= 1.0 { return 1.0; }
+
+    let mut total = signals.name * opts.name_weight;
+    let mut weight = opts.name_weight;
+    for (score, w) in [(signals.embedding, opts.embedding_weight), (signals.context, opts.context_weight)] {
+        if let Some(s) = score {
+            total += s * w;
+            weight += w;
+        }
+    }
+    if weight <= 0.0 { 0.0 } else { total / weight }
+}
+
+impl EntityRow {
+    fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+
+    /// 이름과 별칭의 블로킹 키 (비교용 이름이 비어 있으면 키 없음)
+    fn block_keys(&self) -> HashSet<String> {
+        self.names()
+            .map(|n| canonical_name(n).chars().take(BLOCK_KEY_CHARS).collect::<String>())
+            .filter(|k| !k.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(key: &str, name: &str, embedding: Vec<f32>) -> EntityRow {
+        EntityRow { id: Thing::from(("entity", key)), name: name.to_string(), aliases: vec![], embedding }
+    }
+
+    #[test]
+    fn pairs_from_name_blocks() {
+        let entities = vec![
+            entity("a", "삼성전자", vec![]),
+            entity("b", "삼성", vec![]),
+            entity("c", "LG전자", vec![]),
+        ];
+        assert_eq!(candidate_pairs(&entities, &[]), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn embedding_neighbours_pair_differently_spelled_names() {
+        let entities = vec![
+            entity("a", "OpenAI", vec![0.9, 0.1, 0.0]),
+            entity("b", "오픈AI", vec![0.88, 0.12, 0.01]),
+        ];
+        // 앞 글자가 달라 이름 블록만으로는 비교하지 않음
+        assert!(candidate_pairs(&entities, &[]).is_empty());
+
+        // KNN 결과(자기 자신 포함, 양방향)가 들어오면 한 쌍으로 비교
+        let neighbours = [(0, 0), (0, 1), (1, 1), (1, 0)];
+        assert_eq!(candidate_pairs(&entities, &neighbours), vec![(0, 1)]);
+        assert!(cosine(&entities[0].embedding, &entities[1].embedding).unwrap() > 0.99);
+    }
+}
@@ -0,0 +1,155 @@
+// src/resolution/normalize.rs
+
+use unicode_normalization::UnicodeNormalization;
+
+/// 명사 뒤에 붙는 조사와, 앞 글자의 받침 조건
+#[derive(Clone, Copy, PartialEq)]
+enum Attach {
+    /// 받침 있는 말 뒤 (은, 을, 으로)
+    AfterFinal,
+    /// 받침 없는 말 뒤 (는, 를, 와, 가)
+    AfterVowel,
+    /// 받침과 상관없음 (의, 에서, 까지 ...)
+    Any,
+    /// 영문/숫자 뒤에서만 (과, 이: 한글 명사의 끝 글자와 너무 자주 겹침 - 연구결과, 소꿉놀이)
+    AfterLatin,
+}
+
+/// 긴 조사부터 검사 ("에서는"이 "는"보다 먼저)
+/// 도/로/만처럼 지명, 일반명사 끝 글자와 자주 겹치는 조사는 제외 (경기도, 종로)
+const JOSA: &[(&str, Attach)] = &[
+    ("에게서", Attach::Any),
+    ("에서는", Attach::Any),
+    ("으로서", Attach::AfterFinal),
+    ("으로써", Attach::AfterFinal),
+    ("으로는", Attach::AfterFinal),
+    ("이라는", Attach::AfterFinal),
+    ("이라고", Attach::AfterFinal),
+    ("에서", Attach::Any),
+    ("에게", Attach::Any),
+    ("에는", Attach::Any),
+    ("한테", Attach::Any),
+    ("께서", Attach::Any),
+    ("까지", Attach::Any),
+    ("부터", Attach::Any),
+    ("처럼", Attach::Any),
+    ("보다", Attach::Any),
+    ("으로", Attach::AfterFinal),
+    ("라는", Attach::AfterVowel),
+    ("라고", Attach::AfterVowel),
+    ("은", Attach::AfterFinal),
+    ("을", Attach::AfterFinal),
+    ("과", Attach::AfterLatin),
+    ("이", Attach::AfterLatin),
+    ("는", Attach::AfterVowel),
+    ("를", Attach::AfterVowel),
+    ("와", Attach::AfterVowel),
+    ("가", Attach::AfterVowel),
+    ("의", Attach::Any),
+    ("에", Attach::Any),
+];
+
+/// 조사처럼 보이지만 단어의 일부인 끝말 (민주주의, 정치가 ...)
+const JOSA_EXCEPTIONS: &[&str] = &[
+    "주의", "회의", "강의", "정의", "논의", "협의", "합의", "동의", "편의",
+    "정치가", "만화가", "외교가",
+];
+
+/// 조사를 떼고 남아야 하는 최소 글자 수 (한 글자 명사는 조사와 구분하기 어려움)
+const MIN_STEM_SYLLABLES: usize = 2;
+
+/// Entity 이름 정규화: 전각/반각 통일 -> NFC -> 공백 정리 -> 마지막 어절의 조사 제거
+/// 예: "삼성전자는" -> "삼성전자", "ＯｐｅｎＡＩ의" -> "OpenAI"
+pub fn normalize_entity_name(name: &str) -> String {
+    let unified: String = name.chars().flat_map(unify_width).collect();
+    let nfc: String = unified.nfc().collect();
+    let mut words: Vec<&str> = nfc.split_whitespace().collect();
+
+    let Some(last) = words.pop() else { return String::new() };
+    let stripped = strip_josa(last);
+    words.push(stripped);
+    words.join(" ")
+}
+
+/// 전각 ASCII(Ａ, １, ！)와 전각 공백은 반각으로, 반각 가타카나/한글 자모(ｶ, ﾡ)는 전각으로
+fn unify_width(c: char) -> Vec<char> {
+    match c {
+        '\u{3000}' => vec![' '],
+        '\u{FF01}'..='\u{FF5E}' => vec![char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)],
+        '\u{FF61}'..='\u{FFDC}' => c.to_string().nfkc().collect(),
+        _ => vec![c],
+    }
+}
+
+/// 한글로 끝나는 어절에서 조사를 한 번 제거합니다. (받침 조건이 맞지 않으면 단어의 일부로 봄)
+fn strip_josa(word: &str) -> &str {
+    if JOSA_EXCEPTIONS.iter().any(|e| word.ends_with(e)) {
+        return word;
+    }
+
+    for (josa, attach) in JOSA {
+        let Some(stem) = word.strip_suffix(josa) else { continue };
+        let Some(prev) = stem.chars().last() else { continue };
+        if stem.chars().count() < MIN_STEM_SYLLABLES { continue; }
+
+        // 영문/숫자 뒤의 한글은 조사로 봄 ("OpenAI의", "Apple이")
+        let ok = if is_hangul_syllable(prev) {
+            let has_final = has_final_consonant(prev);
+            match attach {
+                Attach::AfterFinal => has_final,
+                Attach::AfterVowel => !has_final,
+                Attach::Any => true,
+                Attach::AfterLatin => false,
+            }
+        } else {
+            prev.is_ascii_alphanumeric()
+        };
+        if ok { return stem; }
+    }
+    word
+}
+
+fn is_hangul_syllable(c: char) -> bool {
+    ('\u{AC00}'..='\u{D7A3}').contains(&c)
+}
+
+/// 받침 유무 (한글 음절 = 0xAC00 + (초성 * 21 + 중성) * 28 + 종성)
+fn has_final_consonant(c: char) -> bool {
+    !(c as u32 - 0xAC00).is_multiple_of(28)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_entity_name;
+
+    #[test]
+    fn strips_josa_from_last_word() {
+        assert_eq!(normalize_entity_name("삼성전자는"), "삼성전자");
+        assert_eq!(normalize_entity_name("서울에서"), "서울");
+        assert_eq!(normalize_entity_name("대한민국 정부를"), "대한민국 정부");
+        assert_eq!(normalize_entity_name("국립중앙도서관으로"), "국립중앙도서관");
+    }
+
+    #[test]
+    fn unifies_width_and_strips_josa_after_latin() {
+        assert_eq!(normalize_entity_name("ＯｐｅｎＡＩ의"), "OpenAI");
+        assert_eq!(normalize_entity_name("Google이"), "Google");
+        assert_eq!(normalize_entity_name("  Apple   Inc.  "), "Apple Inc.");
+    }
+
+    #[test]
+    fn keeps_noun_endings_that_look_like_josa() {
+        for word in ["연구결과", "경영성과", "컴퓨터공학과", "소꿉놀이", "고양이", "민주주의", "정치가", "경기도", "종로"] {
+            assert_eq!(normalize_entity_name(word), word);
+        }
+    }
+
+    #[test]
+    fn requires_matching_final_consonant() {
+        assert_eq!(normalize_entity_name("기술은"), "기술");
+        assert_eq!(normalize_entity_name("바다를"), "바다");
+        // 받침 조건이 맞지 않으면 단어의 일부로 봄
+        assert_eq!(normalize_entity_name("기술는"), "기술는");
+        assert_eq!(normalize_entity_name("바다을"), "바다을");
+    }
+}
@@ -0,0 +1,75 @@
+// src/resolution/similarity.rs
+
+use std::collections::HashSet;
+
+use super::normalize::normalize_entity_name;
+
+/// 이름 끝에 붙어도 같은 대상을 가리키는 법인 접미사 (비교 시 제거)
+const ORG_SUFFIXES: &[&str] = &[
+    "inc", "incorporated", "corp", "corporation", "co", "company", "ltd", "limited",
+    "llc", "plc", "gmbh", "group", "주식회사", "㈜", "(주)",
+];
+
+/// 비교용 이름: 정규화(조사, 전각/반각), 소문자, 법인 접미사 제거, 문자/숫자 외 제거
+/// 예: "Apple Inc." -> "apple", "(주)삼성전자는" -> "삼성전자"
+pub fn canonical_name(name: &str) -> String {
+    let lower = normalize_entity_name(name).to_lowercase();
+    let mut words: Vec<&str> = lower
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    // 접미사만으로 된 이름("Group")은 그대로 둠
+    while words.len() > 1 && is_org_suffix(words[words.len() - 1]) {
+        words.pop();
+    }
+    while words.len() > 1 && is_org_suffix(words[0]) {
+        words.remove(0);
+    }
+
+    let joined = words.join("");
+    // 붙어 있는 한국어 접두/접미 표기 ("(주)삼성전자", "삼성전자㈜")
+    let joined = ["(주)", "㈜", "주식회사"].iter()
+        .fold(joined, |s, p| {
+            let s = s.strip_prefix(p).map(str::to_string).unwrap_or(s);
+            s.strip_suffix(p).map(str::to_string).unwrap_or(s)
+        });
+
+    joined.chars().filter(|c| c.is_alphanumeric()).collect()
+}
+
+fn is_org_suffix(word: &str) -> bool {
+    ORG_SUFFIXES.contains(&word.trim_end_matches('.'))
+}
+
+/// 이름 유사도 (0~1): 비교용 이름이 같으면 1, 아니면 Jaro-Winkler와 정규화 편집 거리 중 큰 값
+pub fn name_similarity(a: &str, b: &str) -> f32 {
+    let (a, b) = (canonical_name(a), canonical_name(b));
+    if a.is_empty() || b.is_empty() { return 0.0; }
+    if a == b { return 1.0; }
+
+    let jw = strsim::jaro_winkler(&a, &b);
+    let lev = strsim::normalized_levenshtein(&a, &b);
+    jw.max(lev) as f32
+}
+
+/// 코사인 유사도 (길이가 다르거나 비어 있으면 None)
+pub fn cosine(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.is_empty() || a.len() != b.len() { return None; }
+    let (mut dot, mut na, mut nb) = (0.0f32, 0.0f32, 0.0f32);
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        na += x * x;
+        nb += y * y;
+    }
+    if na == 0.0 || nb == 0.0 { return None; }
+    Some(dot / (na.sqrt() * nb.sqrt()))
+}
+
+/// 두 집합의 Jaccard 계수 (둘 다 비어 있으면 None)
+pub fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> Option<f32> {
+    if a.is_empty() && b.is_empty() { return None; }
+    let inter = a.intersection(b).count();
+    let union = a.len() + b.len() - inter;
+    Some(inter as f32 / union as f32)
+}
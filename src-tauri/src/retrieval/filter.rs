@@ -0,0 +1,80 @@
+// src/retrieval/filter.rs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::engine::local::Db;
+use surrealdb::method::Query;
+use surrealdb::sql::Thing;
+
+/// 검색 대상 Chunk를 좁히는 필터
+/// 모든 조건은 AND로 결합되며, 비어있는 항목은 무시됩니다.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct RetrievalFilter {
+    /// 문서 ID ("document:⟨uuid⟩" 또는 uuid)
+    pub document_ids: Vec<String>,
+    /// 문서 생성일(DocumentNode.created_at) 범위
+    pub date_from: Option<DateTime<Utc>>,
+    pub date_to: Option<DateTime<Utc>>,
+    /// Chunk가 언급하는 Entity의 카테고리 중 하나라도 일치
+    pub entity_categories: Vec<String>,
+    /// 분석 결과의 텍스트 종류 (detailed_data.type: Code, Meeting, News, Paper ...)
+    pub analysis_types: Vec<String>,
+    /// Ingest 세션(event) ID
+    pub event_id: Option<String>,
+}
+
+impl RetrievalFilter {
+    /// Chunk 테이블 쿼리의 WHERE 절 뒤에 붙일 조건을 만듭니다. (" AND (...)" 형태, 조건이 없으면 빈 문자열)
+    /// 바인딩 변수는 `bind`로 채워야 합니다.
+    pub fn condition(&self) -> String {
+        let mut parts: Vec<&str> = Vec::new();
+
+        if !self.document_ids.is_empty() {
+            parts.push("array::first(<-contains<-document) INSIDE $f_docs");
+        }
+        // 부모 Document가 없는 Chunk는 NONE을 datetime으로 바꾸다 쿼리 전체가 실패하므로 먼저 거름 (AND는 앞 조건이 거짓이면 뒤를 평가하지 않음)
+        if self.date_from.is_some() {
+            parts.push("array::first(<-contains<-document.created_at) != NONE AND <datetime> array::first(<-contains<-document.created_at) >= <datetime> $f_from");
+        }
+        if self.date_to.is_some() {
+            parts.push("array::first(<-contains<-document.created_at) != NONE AND <datetime> array::first(<-contains<-document.created_at) <= <datetime> $f_to");
+        }
+        if !self.entity_categories.is_empty() {
+            parts.push("->mentions->entity.category CONTAINSANY $f_categories");
+        }
+        if !self.analysis_types.is_empty() {
+            parts.push("string::lowercase(metadata.analysis.detailed_data.type ?? '') INSIDE $f_types");
+        }
+        if self.event_id.is_some() {
+            parts.push("array::first(<-contains<-document<-imported<-event) = $f_event");
+        }
+
+        if parts.is_empty() { String::new() } else { format!(" AND ({})", parts.join(" AND ")) }
+    }
+
+    /// `condition()`에서 사용하는 변수를 쿼리에 바인딩합니다.
+    pub fn bind<'r>(&self, query: Query<'r, Db>) -> Query<'r, Db> {
+        let docs: Vec<Thing> = self.document_ids.iter().map(|id| to_thing("document", id)).collect();
+        let types: Vec<String> = self.analysis_types.iter().map(|t| t.trim().to_lowercase()).collect();
+        let event = self.event_id.as_deref().map(|id| to_thing("event", id));
+
+        query
+            .bind(("f_docs", docs))
+            .bind(("f_from", self.date_from))
+            .bind(("f_to", self.date_to))
+            .bind(("f_categories", self.entity_categories.clone()))
+            .bind(("f_types", types))
+            .bind(("f_event", event))
+    }
+}
+
+/// "table:id" 형태면 그대로 파싱하고, 아니면 `table`의 ID로 간주합니다.
+fn to_thing(table: &str, id: &str) -> Thing {
+    if id.contains(':') {
+        if let Ok(thing) = surrealdb::sql::thing(id) {
+            return thing;
+        }
+    }
+    Thing::from((table, id))
+}
@@ -0,0 +1,36 @@
+// src/retrieval/fulltext.rs
+
+use surrealdb::engine::local::Db;
+use surrealdb::Surreal;
+
+use crate::models::RetrievedChunk;
+use super::filter::RetrievalFilter;
+use super::{ChunkRow, CHUNK_FIELDS};
+
+/// `chunk_content_search` 전문 인덱스로 BM25 키워드 검색을 수행합니다.
+/// 결과는 BM25 점수 내림차순으로 정렬됩니다.
+pub async fn keyword_search(
+    db: &Surreal<Db>,
+    query: &str,
+    top_k: usize,
+    filter: &RetrievalFilter,
+) -> anyhow::Result<Vec<RetrievedChunk>> {
+    if query.trim().is_empty() || top_k == 0 { return Ok(vec![]); }
+
+    let sql = format!(
+        "SELECT {CHUNK_FIELDS}, search::score(1) AS score
+         FROM chunk
+         WHERE content @1@ $query{}
+         ORDER BY score DESC
+         LIMIT $limit",
+        filter.condition()
+    );
+
+    let rows: Vec<ChunkRow> = filter.bind(db.query(sql))
+        .bind(("query", query.to_string()))
+        .bind(("limit", top_k))
+        .await?
+        .take(0)?;
+
+    Ok(rows.into_iter().map(|r| r.into_retrieved("bm25")).collect())
+}
@@ -0,0 +1,403 @@
+// src/retrieval/graph.rs
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use surrealdb::engine::local::Db;
+use surrealdb::sql::Thing;
+use surrealdb::Surreal;
+
+use crate::commands::query::{GraphLinkRes, GraphNodeRes, GraphResponse};
+use crate::database::ensure_vector_index;
+use crate::models::RetrievedChunk;
+use super::filter::RetrievalFilter;
+use super::{ChunkRow, CHUNK_FIELDS};
+
+/// 이름 매칭용 Entity 목록을 다시 읽는 주기 (그 사이 새로 생긴 Entity는 임베딩으로만 시작점이 됨)
+const ENTITY_NAME_TTL: Duration = Duration::from_secs(60);
+
+/// Graph-RAG 탐색 옵션
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct GraphSearchOptions {
+    /// 시작 Entity에서 따라갈 최대 Edge 수 (entity -> chunk -> entity -> chunk = 3 hops)
+    pub max_hops: usize,
+    /// 시작점으로 사용할 Entity 최대 개수
+    pub seed_limit: usize,
+    /// 임베딩으로 찾은 시작 Entity의 최소 유사도
+    pub min_seed_similarity: f32,
+    /// hop 하나를 지날 때마다 경로 점수에 곱해지는 감쇠율
+    pub hop_decay: f32,
+    /// 최종 점수에서 경로 점수가 차지하는 비중 (나머지는 질문-Chunk 유사도)
+    pub path_weight: f32,
+    pub top_k: usize,
+    /// 탐색은 전체 그래프에서 하되, 컨텍스트로 반환할 Chunk에만 적용
+    pub filter: RetrievalFilter,
+}
+
+impl Default for GraphSearchOptions {
+    fn default() -> Self {
+        Self {
+            max_hops: 3,
+            seed_limit: 5,
+            min_seed_similarity: 0.6,
+            hop_decay: 0.7,
+            path_weight: 0.5,
+            top_k: 5,
+            filter: RetrievalFilter::default(),
+        }
+    }
+}
+
+/// Chunk가 선택된 이유 (시작 Entity부터 Chunk까지의 경로)
+#[derive(Debug, Serialize, Clone)]
+pub struct GraphEvidence {
+    pub chunk_id: String,
+    pub seed_entity: String,
+    pub hops: usize,
+    /// 시작 Entity -> ... -> Chunk 순서의 노드 ID 목록
+    pub path: Vec<String>,
+}
+
+/// Graph-RAG 검색 결과: 컨텍스트 Chunk + 탐색한 서브그래프
+#[derive(Debug, Serialize)]
+pub struct GraphRetrieval {
+    pub chunks: Vec<RetrievedChunk>,
+    pub evidence: Vec<GraphEvidence>,
+    /// GraphVisualizer에서 바로 그릴 수 있는 형태 (fetch_graph_data와 동일)
+    pub subgraph: GraphResponse,
+}
+
+#[derive(Debug, Deserialize)]
+struct EntityRow {
+    id: Thing,
+    name: String,
+    #[serde(default)]
+    category: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    score: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct EntityNameRow {
+    id: Thing,
+    name: String,
+}
+
+/// (Entity ID, 소문자 이름) 목록
+type EntityNames = Arc<Vec<(Thing, String)>>;
+
+/// 질문에 이름이 들어 있는 Entity를 찾기 위한 이름 목록
+/// 질문마다 entity 테이블 전체에 string::contains를 돌리지 않도록 AppState에 두고 주기적으로 다시 읽음
+#[derive(Default)]
+pub struct EntityNameCache {
+    names: Mutex<Option<(Instant, EntityNames)>>,
+}
+
+impl EntityNameCache {
+    async fn get(&self, db: &Surreal<Db>) -> surrealdb::Result<EntityNames> {
+        if let Some((loaded_at, names)) = self.names.lock().unwrap().as_ref() {
+            if loaded_at.elapsed() < ENTITY_NAME_TTL { return Ok(names.clone()); }
+        }
+
+        let rows: Vec<EntityNameRow> = db.query("SELECT id, name FROM entity").await?.take(0)?;
+        // 한 글자 이름은 질문 어디에나 들어 있으므로 제외
+        let names: EntityNames = Arc::new(rows.into_iter()
+            .map(|r| (r.id, r.name.trim().to_lowercase()))
+            .filter(|(_, name)| name.chars().count() >= 2)
+            .collect());
+
+        *self.names.lock().unwrap() = Some((Instant::now(), names.clone()));
+        Ok(names)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MentionRow {
+    chunk: Thing,
+    entity: Thing,
+}
+
+#[derive(Debug, Deserialize)]
+struct RelatedRow {
+    source: Thing,
+    target: Thing,
+    relation: Option<String>,
+}
+
+/// BFS 방문 기록
+struct Visit {
+    hops: usize,
+    path_score: f32,
+    seed: String,
+    parent: Option<String>,
+}
+
+/// 질문과 관련된 Entity에서 출발해 `mentions` / `related_to` Edge를 따라가며 Chunk를 모읍니다.
+pub async fn graph_search(
+    db: &Surreal<Db>,
+    names: &EntityNameCache,
+    question: &str,
+    query_vec: Vec<f32>,
+    opts: &GraphSearchOptions,
+) -> anyhow::Result<GraphRetrieval> {
+    // 1. 시작 Entity 찾기 (이름 매칭 + 임베딩 유사도)
+    let seeds = find_seed_entities(db, names, question, &query_vec, opts).await?;
+
+    let mut entities: HashMap<String, EntityRow> = HashMap::new();
+    let mut things: HashMap<String, Thing> = HashMap::new();
+    let mut visits: HashMap<String, Visit> = HashMap::new();
+    let mut frontier_entities: Vec<Thing> = Vec::new();
+    let mut frontier_chunks: Vec<Thing> = Vec::new();
+
+    for seed in seeds {
+        let key = seed.id.to_string();
+        visits.insert(key.clone(), Visit { hops: 0, path_score: seed.score, seed: seed.name.clone(), parent: None });
+        things.insert(key.clone(), seed.id.clone());
+        frontier_entities.push(seed.id.clone());
+        entities.insert(key, seed);
+    }
+
+    // 2. BFS (hop마다 Edge 한 단계씩)
+    let mut links: Vec<GraphLinkRes> = Vec::new();
+    let mut seen_links: HashSet<(String, String)> = HashSet::new();
+
+    for hop in 1..=opts.max_hops {
+        let mut next_entities = Vec::new();
+        let mut next_chunks = Vec::new();
+
+        if !frontier_entities.is_empty() {
+            // Entity -> (mentions) -> Chunk
+            let mentions: Vec<MentionRow> = db
+                .query("SELECT in AS chunk, out AS entity FROM mentions WHERE out INSIDE $ids")
+                .bind(("ids", frontier_entities.clone()))
+                .await?.take(0)?;
+
+            for m in mentions {
+                let (c, e) = (m.chunk.to_string(), m.entity.to_string());
+                push_link(&mut links, &mut seen_links, &c, &e, None);
+                if visit(&mut visits, &c, &e, hop, opts.hop_decay) {
+                    things.insert(c, m.chunk.clone());
+                    next_chunks.push(m.chunk);
+                }
+            }
+
+            // Entity -> (related_to) -> Entity (양방향)
+            let related: Vec<RelatedRow> = db
+                .query("SELECT in AS source, out AS target, relation FROM related_to WHERE in INSIDE $ids OR out INSIDE $ids")
+                .bind(("ids", frontier_entities.clone()))
+                .await?.take(0)?;
+
+            for r in related {
+                let (s, t) = (r.source.to_string(), r.target.to_string());
+                push_link(&mut links, &mut seen_links, &s, &t, r.relation.clone());
+
+                let (from, to, to_thing) = if visits.get(&s).is_some_and(|v| v.hops == hop - 1) {
+                    (s, t, r.target)
+                } else {
+                    (t, s, r.source)
+                };
+                if visit(&mut visits, &to, &from, hop, opts.hop_decay) {
+                    things.insert(to, to_thing.clone());
+                    next_entities.push(to_thing);
+                }
+            }
+        }
+
+        if !frontier_chunks.is_empty() {
+            // Chunk -> (mentions) -> Entity
+            let mentions: Vec<MentionRow> = db
+                .query("SELECT in AS chunk, out AS entity FROM mentions WHERE in INSIDE $ids")
+                .bind(("ids", frontier_chunks.clone()))
+                .await?.take(0)?;
+
+            for m in mentions {
+                let (c, e) = (m.chunk.to_string(), m.entity.to_string());
+                push_link(&mut links, &mut seen_links, &c, &e, None);
+                if visit(&mut visits, &e, &c, hop, opts.hop_decay) {
+                    things.insert(e, m.entity.clone());
+                    next_entities.push(m.entity);
+                }
+            }
+        }
+
+        if next_entities.is_empty() && next_chunks.is_empty() { break; }
+        frontier_entities = next_entities;
+        frontier_chunks = next_chunks;
+    }
+
+    // 3. 방문한 Chunk 점수 계산 (경로 점수 + 질문 유사도)
+    let chunk_ids: Vec<Thing> = things.values().filter(|t| t.tb == "chunk").cloned().collect();
+    let sql = format!(
+        "SELECT {CHUNK_FIELDS},
+            (IF array::len(embedding ?? []) > 0 AND array::len($vec) > 0
+                THEN vector::similarity::cosine(embedding, $vec) ELSE 0 END) AS score
+         FROM chunk WHERE id INSIDE $ids{}",
+        opts.filter.condition()
+    );
+    let rows: Vec<ChunkRow> = if chunk_ids.is_empty() { vec![] } else {
+        opts.filter.bind(db.query(sql))
+            .bind(("vec", query_vec))
+            .bind(("ids", chunk_ids))
+            .await?.take(0)?
+    };
+
+    let mut scored: Vec<RetrievedChunk> = rows.into_iter().map(|row| {
+        let mut chunk = row.into_retrieved("graph");
+        let path_score = visits.get(&chunk.id).map(|v| v.path_score).unwrap_or(0.0);
+        chunk.score = opts.path_weight * path_score + (1.0 - opts.path_weight) * chunk.score;
+        chunk
+    }).collect();
+    scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    // 4. 서브그래프 구성 (방문한 Entity 정보 보충)
+    let missing: Vec<Thing> = things.iter()
+        .filter(|(k, t)| t.tb == "entity" && !entities.contains_key(*k))
+        .map(|(_, t)| t.clone())
+        .collect();
+    if !missing.is_empty() {
+        let rows: Vec<EntityRow> = db
+            .query("SELECT id, name, category, description FROM entity WHERE id INSIDE $ids")
+            .bind(("ids", missing))
+            .await?.take(0)?;
+        for e in rows { entities.insert(e.id.to_string(), e); }
+    }
+
+    let selected: HashSet<String> = scored.iter().take(opts.top_k).map(|c| c.id.clone()).collect();
+    let mut nodes: Vec<GraphNodeRes> = entities.values().map(|e| GraphNodeRes {
+        id: e.id.to_string(),
+        group: "entity".into(),
+        label: e.name.clone(),
+        info: Some(format!("[{}] {}", e.category, e.description)),
+        val: if visits.get(&e.id.to_string()).is_some_and(|v| v.hops == 0) { 14.0 } else { 10.0 },
+    }).collect();
+    nodes.extend(scored.iter().map(|c| GraphNodeRes {
+        id: c.id.clone(),
+        group: "chunk".into(),
+        label: format!("p.{}: {}", c.page_number, c.filename),
+        info: Some(format!("score {:.3} | {}...", c.score, c.content.chars().take(50).collect::<String>())),
+        val: if selected.contains(&c.id) { 8.0 } else { 5.0 },
+    }));
+
+    // 노드로 내보내지 않은 Chunk로 이어지는 Edge는 끝점이 없으므로 제외
+    let node_ids: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+    links.retain(|l| node_ids.contains(l.source.as_str()) && node_ids.contains(l.target.as_str()));
+
+    scored.truncate(opts.top_k);
+    let evidence = scored.iter().filter_map(|c| {
+        let v = visits.get(&c.id)?;
+        Some(GraphEvidence {
+            chunk_id: c.id.clone(),
+            seed_entity: v.seed.clone(),
+            hops: v.hops,
+            path: trace_path(&visits, &c.id),
+        })
+    }).collect();
+
+    Ok(GraphRetrieval { chunks: scored, evidence, subgraph: GraphResponse { nodes, links } })
+}
+
+/// 이름이 질문에 포함된 Entity와 임베딩이 가까운 Entity를 합쳐 시작점으로 사용합니다.
+async fn find_seed_entities(
+    db: &Surreal<Db>,
+    names: &EntityNameCache,
+    question: &str,
+    query_vec: &[f32],
+    opts: &GraphSearchOptions,
+) -> anyhow::Result<Vec<EntityRow>> {
+    let question = question.trim().to_lowercase();
+    if question.is_empty() { return Ok(vec![]); }
+
+    let mut seeds: HashMap<String, EntityRow> = HashMap::new();
+
+    // 이름 매칭은 메모리의 이름 목록에서 (긴 이름 우선: "삼성전자"가 "삼성"보다 구체적)
+    let cached = names.get(db).await?;
+    let mut matched: Vec<&(Thing, String)> = cached.iter()
+        .filter(|(_, name)| question.contains(name.as_str()))
+        .collect();
+    matched.sort_by_key(|(_, name)| std::cmp::Reverse(name.chars().count()));
+    let ids: Vec<Thing> = matched.iter().take(opts.seed_limit).map(|(id, _)| id.clone()).collect();
+
+    if !ids.is_empty() {
+        let by_name: Vec<EntityRow> = db
+            .query("SELECT id, name, category, description, 1.0 AS score FROM $ids")
+            .bind(("ids", ids))
+            .await?.take(0)?;
+        for e in by_name { seeds.insert(e.id.to_string(), e); }
+    }
+
+    if !query_vec.is_empty() {
+        ensure_vector_index(db, "entity", query_vec.len()).await?;
+        let k = opts.seed_limit;
+        let ef = (k * 2).max(40);
+        let sql = format!(
+            "SELECT id, name, category, description, vector::similarity::cosine(embedding, $vec) AS score
+             FROM entity WHERE embedding <|{k},{ef}|> $vec"
+        );
+        let by_vec: Vec<EntityRow> = db.query(sql)
+            .bind(("vec", query_vec.to_vec()))
+            .await?.take(0)?;
+
+        for e in by_vec.into_iter().filter(|e| e.score >= opts.min_seed_similarity) {
+            let key = e.id.to_string();
+            match seeds.get(&key) {
+                Some(existing) if existing.score >= e.score => {}
+                _ => { seeds.insert(key, e); }
+            }
+        }
+    }
+
+    let mut seeds: Vec<EntityRow> = seeds.into_values().collect();
+    seeds.sort_by(|a, b| b.score.total_cmp(&a.score));
+    seeds.truncate(opts.seed_limit);
+    Ok(seeds)
+}
+
+/// 처음 방문한 노드면 기록하고 true를 반환합니다.
+/// 같은 hop에서 더 높은 경로 점수로 다시 도달하면 점수/부모만 갱신합니다.
+fn visit(visits: &mut HashMap<String, Visit>, key: &str, parent: &str, hop: usize, decay: f32) -> bool {
+    let Some(p) = visits.get(parent) else { return false };
+    let path_score = p.path_score * decay;
+    let seed = p.seed.clone();
+
+    match visits.get_mut(key) {
+        Some(existing) => {
+            if existing.hops == hop && existing.path_score < path_score {
+                existing.path_score = path_score;
+                existing.seed = seed;
+                existing.parent = Some(parent.to_string());
+            }
+            false
+        }
+        None => {
+            visits.insert(key.to_string(), Visit { hops: hop, path_score, seed, parent: Some(parent.to_string()) });
+            true
+        }
+    }
+}
+
+fn trace_path(visits: &HashMap<String, Visit>, key: &str) -> Vec<String> {
+    let mut path = vec![key.to_string()];
+    let mut current = key;
+    while let Some(parent) = visits.get(current).and_then(|v| v.parent.as_deref()) {
+        path.push(parent.to_string());
+        current = parent;
+    }
+    path.reverse();
+    path
+}
+
+fn push_link(
+    links: &mut Vec<GraphLinkRes>,
+    seen: &mut HashSet<(String, String)>,
+    source: &str,
+    target: &str,
+    label: Option<String>,
+) {
+    if seen.insert((source.to_string(), target.to_string())) {
+        links.push(GraphLinkRes { source: source.to_string(), target: target.to_string(), label });
+    }
+}
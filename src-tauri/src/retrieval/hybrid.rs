@@ -0,0 +1,55 @@
+// src/retrieval/hybrid.rs
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::models::RetrievedChunk;
+
+/// Reciprocal Rank Fusion 가중치
+/// score(d) = Σ weight_r / (rrf_k + rank_r(d))
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct HybridWeights {
+    /// 벡터(의미) 검색 결과의 가중치
+    pub vector: f32,
+    /// BM25(키워드) 검색 결과의 가중치
+    pub bm25: f32,
+    /// 순위 평탄화 상수 (클수록 하위 순위의 영향이 커짐)
+    pub rrf_k: f32,
+}
+
+impl Default for HybridWeights {
+    fn default() -> Self {
+        Self { vector: 1.0, bm25: 1.0, rrf_k: 60.0 }
+    }
+}
+
+/// 벡터 검색과 BM25 검색 결과를 RRF로 합쳐 상위 `top_k`개를 반환합니다.
+/// 양쪽에서 모두 찾은 Chunk는 `retrievers`에 두 검색기가 모두 기록됩니다.
+pub fn fuse(
+    vector_hits: Vec<RetrievedChunk>,
+    bm25_hits: Vec<RetrievedChunk>,
+    weights: &HybridWeights,
+    top_k: usize,
+) -> Vec<RetrievedChunk> {
+    let mut fused: HashMap<String, RetrievedChunk> = HashMap::new();
+
+    for (hits, weight) in [(vector_hits, weights.vector), (bm25_hits, weights.bm25)] {
+        for (rank, hit) in hits.into_iter().enumerate() {
+            let contribution = weight / (weights.rrf_k + rank as f32 + 1.0);
+            fused.entry(hit.id.clone())
+                .and_modify(|existing| {
+                    existing.score += contribution;
+                    for r in &hit.retrievers {
+                        if !existing.retrievers.contains(r) { existing.retrievers.push(r.clone()); }
+                    }
+                })
+                .or_insert(RetrievedChunk { score: contribution, ..hit });
+        }
+    }
+
+    let mut results: Vec<RetrievedChunk> = fused.into_values().collect();
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results.truncate(top_k);
+    results
+}
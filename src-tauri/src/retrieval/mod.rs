@@ -0,0 +1,143 @@
+// src/retrieval/mod.rs
+
+pub mod vector;
+pub mod fulltext;
+pub mod hybrid;
+pub mod graph;
+pub mod rerank;
+pub mod filter;
+
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use surrealdb::sql::Thing;
+
+use crate::llm::embedder::embed_query;
+use crate::models::RetrievedChunk;
+use crate::AppState;
+use filter::RetrievalFilter;
+use fulltext::keyword_search;
+use hybrid::{fuse, HybridWeights};
+use rerank::{rerank, RerankOptions};
+use vector::vector_search;
+
+/// 하이브리드 모드에서 각 검색기가 가져올 후보 수 (top_k의 배수)
+const HYBRID_CANDIDATE_FACTOR: usize = 3;
+
+/// 검색 방식
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchMode {
+    /// 임베딩 KNN (의미 검색)
+    #[default]
+    Vector,
+    /// 전문 검색 BM25 (정확한 용어 매칭)
+    Bm25,
+    /// Vector + BM25를 RRF로 결합
+    Hybrid,
+}
+
+/// 검색 옵션 (search_docs / chat_with_docs 공용)
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct RetrievalOptions {
+    pub top_k: usize,
+    pub mode: SearchMode,
+    pub weights: HybridWeights,
+    pub rerank: RerankOptions,
+    pub filter: RetrievalFilter,
+}
+
+impl Default for RetrievalOptions {
+    fn default() -> Self {
+        Self {
+            top_k: 5,
+            mode: SearchMode::default(),
+            weights: HybridWeights::default(),
+            rerank: RerankOptions::default(),
+            filter: RetrievalFilter::default(),
+        }
+    }
+}
+
+/// 검색 방식에 따라 질문과 관련된 Chunk를 `top_k`개 찾습니다.
+/// 리랭킹이 켜져 있으면 후보를 더 많이 가져와 리랭커로 재정렬합니다.
+pub async fn retrieve(
+    state: &AppState,
+    query: &str,
+    opts: &RetrievalOptions,
+) -> anyhow::Result<Vec<RetrievedChunk>> {
+    let db = &state.db;
+    let top_k = if opts.rerank.enabled { opts.top_k * opts.rerank.candidate_factor.max(1) } else { opts.top_k };
+
+    let results = match opts.mode {
+        SearchMode::Vector => {
+            let query_vec = embed_query(&state.embed_client(), query).await.map_err(|e| anyhow::anyhow!(e))?;
+            vector_search(db, query_vec, top_k, &opts.filter).await?
+        }
+        SearchMode::Bm25 => keyword_search(db, query, top_k, &opts.filter).await?,
+        SearchMode::Hybrid => {
+            let candidates = top_k * HYBRID_CANDIDATE_FACTOR;
+            let query_vec = embed_query(&state.embed_client(), query).await.map_err(|e| anyhow::anyhow!(e))?;
+            let (vector_hits, bm25_hits) = tokio::try_join!(
+                vector_search(db, query_vec, candidates, &opts.filter),
+                keyword_search(db, query, candidates, &opts.filter),
+            )?;
+
+            fuse(vector_hits, bm25_hits, &opts.weights, top_k)
+        }
+    };
+
+    if !opts.rerank.enabled { return Ok(results); }
+
+    // 리랭커 서버가 없거나 실패하면 기존 순위를 그대로 사용
+    let fallback = results.iter().take(opts.top_k).cloned().collect();
+    match rerank(&state.rerank_url(), query, results, &opts.rerank, opts.top_k).await {
+        Ok(reranked) => Ok(reranked),
+        Err(e) => {
+            println!("    ⚠️ Rerank failed, using original order: {}", e);
+            Ok(fallback)
+        }
+    }
+}
+
+/// 검색 쿼리에서 공통으로 SELECT 하는 Chunk 필드 (출처 Document 포함)
+pub(crate) const CHUNK_FIELDS: &str = "
+    id, content, page_index, metadata,
+    array::first(<-contains<-document) AS document,
+    array::first(<-contains<-document.filename) AS filename
+";
+
+/// 검색 쿼리 결과 한 행 (점수는 쿼리마다 다르게 계산됨)
+#[derive(Debug, Deserialize)]
+pub(crate) struct ChunkRow {
+    pub id: Thing,
+    pub content: String,
+    #[serde(default)]
+    pub page_index: usize,
+    #[serde(default)]
+    pub metadata: HashMap<String, JsonValue>,
+    pub document: Option<Thing>,
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub score: f32,
+}
+
+impl ChunkRow {
+    pub fn into_retrieved(self, retriever: &str) -> RetrievedChunk {
+        let page_number = self.metadata.get("page_number")
+            .and_then(|v| v.as_u64())
+            .map(|n| n as usize)
+            .unwrap_or(self.page_index + 1);
+
+        RetrievedChunk {
+            id: self.id.to_string(),
+            content: self.content,
+            score: self.score,
+            document_id: self.document.map(|d| d.to_string()),
+            filename: self.filename.unwrap_or_else(|| "Unknown".to_string()),
+            page_number,
+            retrievers: vec![retriever.to_string()],
+        }
+    }
+}
@@ -0,0 +1,76 @@
+// src/retrieval/rerank.rs
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::models::RetrievedChunk;
+
+/// 리랭킹 옵션
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct RerankOptions {
+    pub enabled: bool,
+    /// 이 점수(0~1, sigmoid 정규화) 미만인 Chunk는 결과에서 제외
+    pub min_score: f32,
+    /// 리랭커에 넘길 후보 수 (top_k의 배수)
+    pub candidate_factor: usize,
+}
+
+impl Default for RerankOptions {
+    fn default() -> Self {
+        Self { enabled: false, min_score: 0.1, candidate_factor: 4 }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RerankResponse {
+    results: Vec<RerankResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RerankResult {
+    index: usize,
+    relevance_score: f32,
+}
+
+/// 리랭커 서버(8082, `--reranking`)로 후보 Chunk를 질문과의 관련도 순으로 다시 정렬합니다.
+/// `min_score` 미만은 버리고 상위 `top_k`개만 반환합니다.
+pub async fn rerank(
+    base_url: &str,
+    query: &str,
+    candidates: Vec<RetrievedChunk>,
+    opts: &RerankOptions,
+    top_k: usize,
+) -> anyhow::Result<Vec<RetrievedChunk>> {
+    if candidates.is_empty() { return Ok(candidates); }
+
+    let payload = json!({
+        "query": query,
+        "documents": candidates.iter().map(|c| c.content.as_str()).collect::<Vec<_>>(),
+        "top_n": candidates.len(),
+    });
+
+    let endpoint = format!("{}/rerank", base_url.trim_end_matches('/'));
+    let res = Client::new().post(&endpoint).json(&payload).send().await?;
+    if !res.status().is_success() {
+        anyhow::bail!("Rerank Request Failed: {}", res.status());
+    }
+    let body: RerankResponse = res.json().await?;
+
+    let mut slots: Vec<Option<RetrievedChunk>> = candidates.into_iter().map(Some).collect();
+    let mut reranked: Vec<RetrievedChunk> = body.results.into_iter()
+        .filter_map(|r| {
+            let mut chunk = slots.get_mut(r.index)?.take()?;
+            // llama-server는 로짓을 그대로 주므로 0~1 범위로 정규화
+            chunk.score = 1.0 / (1.0 + (-r.relevance_score).exp());
+            chunk.retrievers.push("rerank".to_string());
+            Some(chunk)
+        })
+        .filter(|c| c.score >= opts.min_score)
+        .collect();
+
+    reranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    reranked.truncate(top_k);
+    Ok(reranked)
+}
@@ -0,0 +1,41 @@
+// src/retrieval/vector.rs
+
+use surrealdb::engine::local::Db;
+use surrealdb::Surreal;
+
+use crate::database::ensure_vector_index;
+use crate::models::RetrievedChunk;
+use super::filter::RetrievalFilter;
+use super::{ChunkRow, CHUNK_FIELDS};
+
+/// HNSW 인덱스를 이용해 `query_vec`과 가장 가까운 Chunk `top_k`개를 찾습니다.
+/// 결과는 코사인 유사도 내림차순으로 정렬됩니다.
+/// 필터 조건은 KNN 탐색과 같은 WHERE 절에서 적용됩니다.
+pub async fn vector_search(
+    db: &Surreal<Db>,
+    query_vec: Vec<f32>,
+    top_k: usize,
+    filter: &RetrievalFilter,
+) -> anyhow::Result<Vec<RetrievedChunk>> {
+    if query_vec.is_empty() || top_k == 0 { return Ok(vec![]); }
+
+    // 인덱스가 없으면 KNN 연산자를 쓸 수 없으므로 먼저 보장
+    ensure_vector_index(db, "chunk", query_vec.len()).await?;
+
+    // KNN 연산자(<|K,EF|>)의 K, EF는 파라미터 바인딩이 안 되므로 직접 넣습니다.
+    let ef = (top_k * 2).max(40);
+    let sql = format!(
+        "SELECT {CHUNK_FIELDS}, vector::similarity::cosine(embedding, $vec) AS score
+         FROM chunk
+         WHERE embedding <|{top_k},{ef}|> $vec{}
+         ORDER BY score DESC",
+        filter.condition()
+    );
+
+    let rows: Vec<ChunkRow> = filter.bind(db.query(sql))
+        .bind(("vec", query_vec))
+        .await?
+        .take(0)?;
+
+    Ok(rows.into_iter().map(|r| r.into_retrieved("vector")).collect())
+}
@@ -0,0 +1,296 @@
+// src/watcher/mod.rs
+
+use chrono::Utc;
+use notify_debouncer_full::notify::event::ModifyKind;
+use notify_debouncer_full::notify::{EventKind, RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{new_debouncer, DebounceEventResult, DebouncedEvent, Debouncer, RecommendedCache};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use surrealdb::engine::local::Db;
+use surrealdb::sql::Thing;
+use surrealdb::Surreal;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::mpsc;
+
+use crate::ingest::scan::{accepts_file, scan_directory};
+use crate::ingest::{has_documents_under, ingest_file, remove_documents_under, FailedFile, IngestReport, SkippedFile};
+use crate::jobs::{self, JobContext, JobRequest};
+use crate::models::{EventNode, WatchedFolder};
+use crate::AppState;
+
+/// 마지막 변경 후 이만큼 조용해야 처리 (복사 중인 큰 파일을 여러 번 Ingest하지 않도록)
+const DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// 자동 Ingest 결과를 프론트엔드로 보내는 이벤트 이름
+pub const WATCH_EVENT: &str = "watch-ingest";
+
+/// 감시 작업 요청 (폴더별로 Watch 작업을 만들어 작업 대기열에서 처리)
+pub enum WatchMessage {
+    /// 파일 시스템 변경 (debounce된 묶음)
+    Events(Vec<DebouncedEvent>),
+    /// 폴더 전체 동기화 (등록 직후, 앱 시작 시)
+    Sync(PathBuf),
+}
+
+/// 자동 Ingest 결과 (WATCH_EVENT payload)
+#[derive(Debug, Serialize, Clone)]
+pub struct WatchReport {
+    pub root: String,
+    pub report: IngestReport,
+}
+
+pub struct FolderWatcher {
+    debouncer: Debouncer<RecommendedWatcher, RecommendedCache>,
+    sender: mpsc::UnboundedSender<WatchMessage>,
+    roots: Vec<PathBuf>,
+}
+
+impl FolderWatcher {
+    pub fn new(sender: mpsc::UnboundedSender<WatchMessage>) -> Result<Self, String> {
+        let tx = sender.clone();
+        let debouncer = new_debouncer(DEBOUNCE, None, move |result: DebounceEventResult| match result {
+            Ok(events) => { let _ = tx.send(WatchMessage::Events(events)); }
+            Err(errors) => errors.iter().for_each(|e| eprintln!("⚠️ [Watch] {}", e)),
+        }).map_err(|e| e.to_string())?;
+
+        Ok(Self { debouncer, sender, roots: vec![] })
+    }
+
+    /// 폴더 감시를 시작하고 한 번 전체 동기화를 요청합니다.
+    pub fn watch(&mut self, root: &Path) -> Result<(), String> {
+        if !self.roots.iter().any(|r| r == root) {
+            self.debouncer.watch(root, RecursiveMode::Recursive).map_err(|e| e.to_string())?;
+            self.roots.push(root.to_path_buf());
+        }
+        let _ = self.sender.send(WatchMessage::Sync(root.to_path_buf()));
+        Ok(())
+    }
+
+    pub fn unwatch(&mut self, root: &Path) {
+        if !self.roots.iter().any(|r| r == root) { return; }
+        self.roots.retain(|r| r != root);
+        // 감시 중 지워진 하위 폴더가 있으면 inotify가 오류를 내지만 감시는 해제됨
+        if let Err(e) = self.debouncer.unwatch(root) {
+            eprintln!("⚠️ [Watch] {} 감시 해제 중 오류: {}", root.display(), e);
+        }
+    }
+
+    pub fn is_watching(&self, root: &Path) -> bool {
+        self.roots.iter().any(|r| r == root)
+    }
+
+    /// 경로가 속한 감시 폴더 (겹치면 가장 안쪽 폴더)
+    fn root_of(&self, path: &Path) -> Option<PathBuf> {
+        self.roots.iter()
+            .filter(|r| path.starts_with(r))
+            .max_by_key(|r| r.components().count())
+            .cloned()
+    }
+}
+
+/// 감시기를 만들고 DB에 저장된 폴더를 다시 감시한 뒤, 변경 이벤트를 Watch 작업으로 넘깁니다. (setup에서 한 번 실행)
+/// Ingest는 작업 대기열에서만 실행되므로 ingest_documents 작업과 같은 파일을 동시에 처리하지 않습니다.
+pub async fn run(app: AppHandle) {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let state = app.state::<AppState>();
+
+    match FolderWatcher::new(sender) {
+        Ok(watcher) => *state.watcher.lock().unwrap() = Some(watcher),
+        Err(e) => {
+            eprintln!("❌ 폴더 감시 시작 실패: {}", e);
+            return;
+        }
+    }
+
+    // 앱이 꺼져 있던 동안의 변경도 반영되도록 Sync 요청과 함께 등록
+    let folders = list_folders(&state.db).await.unwrap_or_else(|e| {
+        eprintln!("⚠️ 감시 폴더 목록 조회 실패: {}", e);
+        vec![]
+    });
+    for folder in folders {
+        let root = PathBuf::from(&folder.path);
+        let watched = state.watcher.lock().unwrap().as_mut().map(|w| w.watch(&root));
+        if let Some(Err(e)) = watched {
+            eprintln!("⚠️ [Watch] {} 감시 실패: {}", folder.path, e);
+        }
+    }
+
+    while let Some(message) = receiver.recv().await {
+        let changes = match message {
+            WatchMessage::Events(events) => collect_changes(&state, events).await,
+            WatchMessage::Sync(root) => vec![(root, vec![])],
+        };
+
+        for (root, paths) in changes {
+            let request = JobRequest::Watch {
+                root: root.display().to_string(),
+                paths: paths.iter().map(|p| p.display().to_string()).collect(),
+            };
+            if let Err(e) = jobs::submit(&app, request).await {
+                eprintln!("⚠️ [Watch] {} 작업 등록 실패: {}", root.display(), e);
+            }
+        }
+    }
+}
+
+/// 변경된 경로를 감시 폴더별로 모읍니다. (처리할 것이 없는 경로는 작업을 만들지 않도록 미리 거름)
+async fn collect_changes(state: &AppState, events: Vec<DebouncedEvent>) -> Vec<(PathBuf, Vec<PathBuf>)> {
+    let mut changed: BTreeMap<PathBuf, BTreeSet<PathBuf>> = BTreeMap::new();
+    {
+        let guard = state.watcher.lock().unwrap();
+        let Some(watcher) = guard.as_ref() else { return vec![] };
+
+        for event in &events {
+            // 파일을 읽기만 한 이벤트는 무시 (Ingest가 파일을 여는 것도 Access 이벤트가 됨)
+            if matches!(event.kind, EventKind::Access(_)) { continue; }
+            // 폴더가 새로 생기거나 옮겨져 들어온 경우만 그 아래를 다시 훑음 (폴더 자체의 수정 이벤트는 무시)
+            let dir_added = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_)));
+
+            for path in &event.paths {
+                let Some(root) = watcher.root_of(path) else { continue };
+                if !path.is_dir() || dir_added {
+                    changed.entry(root).or_default().insert(path.clone());
+                }
+            }
+        }
+    }
+
+    let scan = state.settings().ingest;
+    let mut changes = Vec::new();
+    for (root, paths) in changed {
+        let mut kept = Vec::new();
+        for path in paths {
+            let keep = if path.is_dir() {
+                true
+            } else if path.exists() {
+                // 건너뛸 이유가 있는 파일(크기 초과 등)은 작업 결과에 남도록 그대로 넘김
+                !matches!(accepts_file(&root, &path, &scan), Ok(None))
+            } else {
+                has_documents_under(&state.db, &path).await.unwrap_or(true)
+            };
+            if keep { kept.push(path); }
+        }
+        if !kept.is_empty() { changes.push((root, kept)); }
+    }
+    changes
+}
+
+/// Watch 작업 본문. 바뀐 경로(또는 폴더 전체)를 Ingest/삭제에 반영하고 WATCH_EVENT로 알립니다.
+pub async fn apply_changes(state: &AppState, ctx: &JobContext, root: &str, paths: Vec<String>) -> Result<IngestReport, String> {
+    let root = PathBuf::from(root);
+    // 작업이 대기하는 동안 감시가 해제된 폴더는 건너뜀
+    let watching = state.watcher.lock().unwrap().as_ref().is_some_and(|w| w.is_watching(&root));
+    if !watching {
+        return Ok(IngestReport::default());
+    }
+
+    let report = if paths.is_empty() {
+        sync_folder(state, ctx, &root).await?
+    } else {
+        apply_paths(state, ctx, &root, paths.into_iter().map(PathBuf::from).collect()).await?
+    };
+
+    let changed = !(report.added.is_empty() && report.updated.is_empty() && report.removed.is_empty() && report.failed.is_empty());
+    println!("👀 [Watch] {}: {}", root.display(), report.summary());
+    if changed {
+        let _ = ctx.app.emit(WATCH_EVENT, WatchReport { root: root.display().to_string(), report: report.clone() });
+    }
+    Ok(report)
+}
+
+async fn apply_paths(state: &AppState, ctx: &JobContext, root: &Path, paths: Vec<PathBuf>) -> Result<IngestReport, String> {
+    let scan = state.settings().ingest;
+    let mut report = IngestReport::default();
+
+    // 새로 생긴 폴더는 그 아래 파일을 모두 대상으로
+    let (dirs, paths): (Vec<PathBuf>, Vec<PathBuf>) = paths.into_iter().partition(|p| p.is_dir());
+    let mut files = BTreeSet::new();
+    if !dirs.is_empty() {
+        let scanned = scan_directory(root, &scan).map_err(|e| e.to_string())?;
+        files.extend(scanned.files.into_iter().map(|f| f.path).filter(|p| dirs.iter().any(|d| p.starts_with(d))));
+    }
+
+    // 이동된 파일이 먼저 새 경로로 옮겨지도록 (재분석 없이) 추가/변경을 삭제보다 먼저 처리
+    let (existing, gone): (Vec<PathBuf>, Vec<PathBuf>) = paths.into_iter().partition(|p| p.exists());
+    for path in existing {
+        match accepts_file(root, &path, &scan) {
+            Ok(Some(_)) => { files.insert(path); }
+            Ok(None) => {}
+            Err(e) => report.skipped.push(SkippedFile { path: path.display().to_string(), reason: e.to_string() }),
+        }
+    }
+    ingest_files(state, ctx, root, files.into_iter().collect(), &mut report).await?;
+
+    for path in gone {
+        ctx.checkpoint()?;
+        remove_path(&state.db, &path, &mut report).await;
+    }
+    Ok(report)
+}
+
+/// 폴더 전체를 다시 훑어 새/바뀐 파일은 Ingest하고, 원본이 사라진 문서는 지웁니다.
+async fn sync_folder(state: &AppState, ctx: &JobContext, root: &Path) -> Result<IngestReport, String> {
+    let scanned = scan_directory(root, &state.settings().ingest).map_err(|e| e.to_string())?;
+    let mut report = IngestReport { skipped: scanned.skipped, ..Default::default() };
+    ingest_files(state, ctx, root, scanned.files.into_iter().map(|f| f.path).collect(), &mut report).await?;
+
+    // 이 폴더에서 온 문서 중 원본 파일이 없어진 것
+    let prefix = format!("{}{}", root.display(), std::path::MAIN_SEPARATOR);
+    let sources: Vec<String> = state.db
+        .query("SELECT VALUE source_path FROM document WHERE string::starts_with(source_path, $prefix)")
+        .bind(("prefix", prefix))
+        .await.map_err(|e| e.to_string())?
+        .take(0).map_err(|e| e.to_string())?;
+    for source in sources.into_iter().map(PathBuf::from).filter(|p| !p.exists()) {
+        ctx.checkpoint()?;
+        remove_path(&state.db, &source, &mut report).await;
+    }
+
+    let _ = state.db.query("UPDATE watched_folder SET last_synced_at = time::now() WHERE path = $path")
+        .bind(("path", root.display().to_string()))
+        .await;
+    Ok(report)
+}
+
+/// ingest_documents와 같은 파이프라인으로 파일들을 처리합니다. 파일 사이마다 일시정지/취소 요청을 확인합니다.
+/// (작업 기록 Event는 처리할 파일이 있을 때만 생성, ID는 작업 ID와 같음)
+async fn ingest_files(state: &AppState, ctx: &JobContext, root: &Path, files: Vec<PathBuf>, report: &mut IngestReport) -> Result<(), String> {
+    if files.is_empty() { return Ok(()); }
+    let chunking = state.settings().chunking;
+
+    let existing: Option<EventNode> = state.db.select(("event", ctx.id.as_str())).await.map_err(|e| e.to_string())?;
+    if existing.is_none() {
+        let _: Option<EventNode> = state.db.create(("event", ctx.id.as_str()))
+            .content(EventNode {
+                id: None, summary: format!("Auto Ingest: {}", root.display()), created_at: Utc::now(),
+            }).await.map_err(|e| e.to_string())?;
+    }
+    let session = Thing::from(("event", ctx.id.as_str()));
+
+    let total = files.len();
+    for (idx, path) in files.iter().enumerate() {
+        ctx.checkpoint()?;
+        ctx.progress(idx, total, path.display().to_string()).await;
+        println!("\n👀 [Watch] Processing: {}", path.display());
+        let result = ingest_file(state, path, Some(root), Some(&session), &chunking).await;
+        report.record(path, result);
+    }
+    ctx.progress(total, total, report.summary()).await;
+    Ok(())
+}
+
+async fn remove_path(db: &Surreal<Db>, path: &Path, report: &mut IngestReport) {
+    match remove_documents_under(db, path).await {
+        Ok(removed) => {
+            for source in &removed { println!("🗑️ [Watch] Removed: {}", source); }
+            report.removed.extend(removed);
+        }
+        Err(e) => report.failed.push(FailedFile { path: path.display().to_string(), error: e.to_string() }),
+    }
+}
+
+pub async fn list_folders(db: &Surreal<Db>) -> surrealdb::Result<Vec<WatchedFolder>> {
+    db.query("SELECT * FROM watched_folder ORDER BY created_at ASC").await?.take(0)
+}
@@ -1,83 +1,64 @@
 //src/components/ChatRoom.tsx
 import { useState } from "react";
-import { invoke } from "@tauri-apps/api/core"; // Rust 호출용
+import { invoke, Channel } from "@tauri-apps/api/core"; // Rust 호출용
+
+// Rust ChatStreamEvent enum과 동일
+type ChatStreamEvent =
+  | { event: "retrieved"; data: { count: number } }
+  | { event: "token"; data: { text: string } };
+
+// Rust Citation 구조체와 동일
+interface Citation {
+  index: number;
+  chunk_id: string;
+  document_id?: string;
+  filename: string;
+  page_number: number;
+  score: number;
+  cited: boolean;
+}
+
+interface ChatAnswer {
+  answer: string;
+  citations: Citation[];
+}
 
 const ChatRoom = () => {
   const [input, setInput] = useState("");
   const [chat, setChat] = useState("");
+  const [citations, setCitations] = useState<Citation[]>([]);
   const [isLoading, setIsLoading] = useState(false);
+  const [conversationId, setConversationId] = useState<string | null>(null);
 
   const askAI = async () => {
     if (!input.trim()) return;
     setIsLoading(true);
-    setChat("");
-    
-    try {
-      // 1. Rust에게 벡터 검색 요청 (관련 문서 가져오기)
-      let context = "";
-      try {
-        setChat("🧬 지식 베이스 검색 중...");
-        context = await invoke<string>("search_docs", { query: input });
-        console.log("🔍 [RAG 검색 결과]:\n", context);
-      } catch (e) {
-        console.error("검색 실패:", e);
-        // 검색 실패해도 대화는 가능하게 빈 문자열 처리
-      }
-
-      // 2. 프롬프트 구성 (Context + Question)
-      // 시스템 프롬프트 느낌으로 구성합니다.
-      const augmentedPrompt = `
-        당신은 사용자가 제공한 문서를 기반으로 답변하는 AI 비서입니다.
-        아래의 [참고 문서]를 바탕으로 질문에 대해 명확하고 정확하게 답변하세요.
-        만약 참고 문서에 내용이 없다면, 일반적인 지식으로 답변하되 문서에 없다고 언급해주세요.
-
-        [참고 문서]
-        ${context}
+    setChat("🧬 지식 베이스 검색 중...");
+    setCitations([]);
 
-        [질문]
-        ${input}
-      `.trim();
-
-      // UI에는 검색 완료 메시지 잠깐 표시 후 답변 시작
-      setChat("🤔 답변 생성 중...");
-
-      // 3. 로컬 LLM에게 전송
-      const res = await fetch("http://localhost:8080/v1/chat/completions", {
-        method: "POST",
-        headers: { "Content-Type": "application/json" },
-        body: JSON.stringify({
-          model: "ggml-model-Q4_K_M", // 실행시킬 때 쓴 모델명과 일치하지 않아도 llama.cpp는 보통 동작함
-          messages: [
-            { role: "system", content: "당신은 도움이 되는 AI 어시스턴트입니다." },
-            { role: "user", content: augmentedPrompt } 
-          ],
-          stream: true,
-        }),
-      });
-
-      if (!res.ok || !res.body) throw new Error("서버 에러");
-
-      const reader = res.body.getReader();
-      const decoder = new TextDecoder();
-      let fullResponse = "";
+    // 검색/생성은 Rust(chat_with_docs)에서 처리하고, 토큰은 Channel로 받습니다.
+    let fullResponse = "";
+    const onEvent = new Channel<ChatStreamEvent>();
+    onEvent.onmessage = (message) => {
+      if (message.event === "retrieved") {
+        setChat(`🤔 답변 생성 중... (참고 문서 ${message.data.count}개)`);
+      } else if (message.event === "token") {
+        fullResponse += message.data.text;
+        setChat(fullResponse); // 화면 갱신
+      }
+    };
 
-      // 4. 스트리밍 응답 처리
-      while (true) {
-        const { done, value } = await reader.read();
-        if (done) break;
-        const chunk = decoder.decode(value, { stream: true });
-        const lines = chunk.split("\n");
-        for (const line of lines) {
-          if (line.startsWith("data: ") && line !== "data: [DONE]") {
-            try {
-              const data = JSON.parse(line.replace("data: ", ""));
-              const content = data.choices[0]?.delta?.content || "";
-              fullResponse += content;
-              setChat(fullResponse); // 화면 갱신
-            } catch (e) {}
-          }
-        }
+    try {
+      // 첫 질문이면 대화(세션)를 만들어 기록이 DB에 남도록 합니다.
+      let convId = conversationId;
+      if (!convId) {
+        const conv = await invoke<{ id: string }>("create_conversation", {});
+        convId = conv.id;
+        setConversationId(convId);
       }
+      const result = await invoke<ChatAnswer>("chat_with_docs", { question: input, conversationId: convId, onEvent });
+      setChat(result.answer);
+      setCitations(result.citations.filter((c) => c.cited));
     } catch (error) {
       setChat("오류가 발생했습니다: " + String(error));
     } finally {
@@ -89,7 +70,12 @@ const ChatRoom = () => {
     <div style={{ display: "flex", flexDirection: "column", height: "100%", padding: "30px" }}>
       <header style={{ marginBottom: "20px" }}>
         <h2 style={{ margin: 0 }}>💬 AI 어시스턴트 (RAG)</h2>
-        <p style={{ color: "#666" }}>PDF 문서 내용을 바탕으로 답변합니다.</p>
+        <p style={{ color: "#666" }}>
+          문서 내용을 바탕으로 답변합니다.{" "}
+          <button onClick={() => { setConversationId(null); setChat(""); setCitations([]); }} disabled={isLoading}>
+            새 대화
+          </button>
+        </p>
       </header>
 
       {/* 답변창 */}
@@ -105,6 +91,13 @@ const ChatRoom = () => {
         fontSize: "1.1rem"
       }}>
         {chat || <span style={{ color: "#aaa" }}>질문을 입력하세요. (예: "무어의 법칙이 뭐야?")</span>}
+        {citations.length > 0 && (
+          <div style={{ marginTop: "20px", paddingTop: "10px", borderTop: "1px solid #eee", fontSize: "0.85rem", color: "#666" }}>
+            {citations.map((c) => (
+              <div key={c.chunk_id}>[{c.index}] {c.filename} p.{c.page_number}</div>
+            ))}
+          </div>
+        )}
       </div>
 
       {/* 입력창 */}
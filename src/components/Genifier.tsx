@@ -1,5 +1,6 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { open } from "@tauri-apps/plugin-dialog";
 import GraphVisualizer from './GraphVisualizer';
 
@@ -28,11 +29,33 @@ interface ChunkData {
 interface DocumentData {
   id: { tb: string, id: { String: string } } | any;
   filename: string;
+  relative_path?: string; // Ingest 폴더 기준 경로
   created_at: string;
   metadata: DocMetadata;
   chunks: ChunkData[]; 
 }
 
+// ingest_documents 결과 (Rust의 IngestReport)
+interface IngestReport {
+  added: string[];
+  updated: string[];
+  skipped: { path: string; reason: string }[];
+  removed: string[];
+  failed: { path: string; error: string }[];
+}
+
+// 백그라운드 작업 (Rust의 jobs::Job, job-update 이벤트 payload)
+interface Job {
+  id: string;
+  request: { kind: "ingest" | "graph" | "embed" | "watch" };
+  status: "queued" | "running" | "paused" | "failed" | "done" | "cancelled";
+  progress: { done: number; total: number; message: string };
+  result?: any;
+  error?: string;
+}
+
+const FINISHED_JOB = ["paused", "failed", "done", "cancelled"];
+
 const DocumentItem = ({ doc }: { doc: DocumentData }) => {
   const [isOpen, setIsOpen] = useState(false);
   
@@ -54,7 +77,7 @@ const DocumentItem = ({ doc }: { doc: DocumentData }) => {
               {meta.title || doc.filename}
             </div>
             <div style={{ color: "#6c7086", fontSize: "0.75rem", marginTop: "2px" }}>
-              {new Date(doc.created_at).toLocaleString()}
+              {doc.relative_path ? `${doc.relative_path} · ` : ""}{new Date(doc.created_at).toLocaleString()}
             </div>
           </div>
         </div>
@@ -109,6 +132,7 @@ export default function Genifier() {
   const [useGpu, setUseGpu] = useState(false);
   const [uiMode, setUiMode] = useState<"graph" | "list">("graph");
   const [documents, setDocuments] = useState<DocumentData[]>([]);
+  const [activeJob, setActiveJob] = useState<Job | null>(null);
 
   // 🔄 문서 목록 불러오기
   const fetchDocuments = async () => {
@@ -122,6 +146,21 @@ export default function Genifier() {
 
   useEffect(() => {
     fetchDocuments();
+    // crisper.toml을 읽지 못했으면 llama-server가 시작되지 않았으므로 원인을 표시
+    invoke<string | null>("get_settings_error").then(error => {
+      if (error) setLog(prev => prev + `\n❌ 설정 파일 오류 (모델 서버를 시작하지 않았습니다): ${error}`);
+    });
+  }, []);
+
+  // 👀 감시 폴더 자동 Ingest 결과 수신 (Rust의 WatchReport)
+  useEffect(() => {
+    const unlisten = listen<{ root: string; report: IngestReport }>("watch-ingest", ({ payload }) => {
+      const r = payload.report;
+      setLog(prev => prev + `\n👀 자동 반영 (${payload.root}): 추가 ${r.added.length}, 갱신 ${r.updated.length}, 삭제 ${r.removed.length}, 실패 ${r.failed.length}`);
+      fetchDocuments();
+      setRefreshGraph(prev => prev + 1);
+    });
+    return () => { unlisten.then(f => f()); };
   }, []);
 
   const handleToggleGpu = async () => {
@@ -160,9 +199,11 @@ export default function Genifier() {
       setStatus("loading");
       setLog(prev => prev + `\n📥 [Step 1] 문서 저장 및 요약 시작...`);
       
-      const result = await invoke<string>("ingest_documents", { path: selectedPath });
+      const job = await invoke<Job>("ingest_documents", { path: selectedPath });
+      const report: IngestReport = await waitForJob(job);
 
-      setLog(prev => prev + `\n✅ 1단계 완료: ${result}`);
+      setLog(prev => prev + `\n✅ 1단계 완료: 추가 ${report.added.length}, 갱신 ${report.updated.length}, 건너뜀 ${report.skipped.length}, 실패 ${report.failed.length}`);
+      report.failed.forEach(f => setLog(prev => prev + `\n   ❌ ${f.path}: ${f.error}`));
       setStatus("success");
       
       await fetchDocuments(); 
@@ -173,15 +214,59 @@ export default function Genifier() {
     }
   };
 
+  // 작업이 끝날 때까지 진행 상황을 표시하며 대기 (완료가 아니면 reject)
+  const waitForJob = async (job: Job) => {
+    setActiveJob(job);
+    const finished = await new Promise<Job>(async (resolve) => {
+      const unlisten = await listen<Job>("job-update", ({ payload }) => {
+        if (payload.id !== job.id) return;
+        setActiveJob(payload);
+        if (FINISHED_JOB.includes(payload.status)) { unlisten(); resolve(payload); }
+      });
+      // 이벤트를 구독하기 전에 이미 끝났을 수 있으므로 한 번 조회
+      const current = await invoke<Job>("get_job", { id: job.id });
+      if (FINISHED_JOB.includes(current.status)) { unlisten(); resolve(current); }
+    });
+    setActiveJob(null);
+    if (finished.status !== "done") throw new Error(finished.error || `작업이 ${finished.status} 상태입니다.`);
+    return finished.result;
+  };
+
+  const handleCancelJob = async () => {
+    if (!activeJob) return;
+    try {
+      await invoke("cancel_job", { id: activeJob.id });
+    } catch (error) {
+      setLog(prev => prev + `\n❌ 작업 취소 실패: ${String(error)}`);
+    }
+  };
+
+  // 선택한 폴더를 감시 목록에 추가 (이후 파일 추가/수정/삭제가 자동 반영됨)
+  const handleWatchFolder = async () => {
+    if (!selectedPath) return;
+    try {
+      await invoke("add_watched_folder", { path: selectedPath });
+      setLog(prev => prev + `\n👀 감시 시작: ${selectedPath}`);
+    } catch (error) {
+      setLog(prev => prev + `\n❌ 감시 등록 실패: ${String(error)}`);
+    }
+  };
+
   // Step 2: 그래프 생성 (Graph Build)
   const handleBuildGraph = async () => {
     try {
       setStatus("loading");
       setLog(prev => prev + `\n🕸️ [Step 2] 지식 그래프 생성 시작... (시간이 걸릴 수 있습니다)`);
       
-      const result = await invoke<string>("construct_graph"); // Rust Backend 호출
+      const job = await invoke<Job>("construct_graph"); // Rust Backend 호출 (백그라운드 작업)
+      const result: string = await waitForJob(job);
 
       setLog(prev => prev + `\n✅ 2단계 완료: ${result}`);
+
+      // Step 3: Entity 간 관계(related_to) 추출
+      setLog(prev => prev + `\n🔗 [Step 3] 관계 추출 시작...`);
+      const relations = await invoke<string>("extract_chunk_relations");
+      setLog(prev => prev + `\n✅ 3단계 완료: ${relations}`);
       setStatus("success");
       
       setRefreshGraph(prev => prev + 1); // 그래프 뷰 갱신 트리거
@@ -234,7 +319,7 @@ export default function Genifier() {
                   </button>
                 </div>
                 <button onClick={handleSelectFolder} title={selectedPath || "폴더 선택"} style={{ width: "100%", padding: "6px 10px", borderRadius: "6px", border: "1px solid #45475a", backgroundColor: "#313244", color: selectedPath ? "#a6e3a1" : "#cdd6f4", cursor: "pointer", textAlign: "left", overflow: "hidden", whiteSpace: "nowrap", fontSize: "0.8rem" }}>
-                  {selectedPath ? `📂 ...${selectedPath.slice(-20)}` : "📂 PDF 폴더 선택"}
+                  {selectedPath ? `📂 ...${selectedPath.slice(-20)}` : "📂 문서 폴더 선택"}
                 </button>
               </div>
 
@@ -245,9 +330,18 @@ export default function Genifier() {
                 <button onClick={handleBuildGraph} disabled={status === "loading"} style={{ width: "100px", borderRadius: "8px", border: "none", backgroundColor: (status === "loading") ? "#45475a" : "#89b4fa", color: "#1e1e2e", fontWeight: "bold", cursor: "pointer", display: "flex", flexDirection: "column", alignItems: "center", justifyContent: "center", gap: "2px" }}>
                   <span style={{ fontSize: "1.2rem" }}>🕸️</span><span style={{ fontSize: "0.75rem" }}>Step 2</span>
                 </button>
+                <button onClick={handleWatchFolder} disabled={!selectedPath} title="폴더 변경 시 자동 Ingest" style={{ width: "100px", borderRadius: "8px", border: "none", backgroundColor: !selectedPath ? "#45475a" : "#a6e3a1", color: "#1e1e2e", fontWeight: "bold", cursor: "pointer", display: "flex", flexDirection: "column", alignItems: "center", justifyContent: "center", gap: "2px" }}>
+                  <span style={{ fontSize: "1.2rem" }}>👀</span><span style={{ fontSize: "0.75rem" }}>Watch</span>
+                </button>
               </div>
 
               <div style={{ flex: 1, backgroundColor: "#11111b", padding: "8px", borderRadius: "6px", border: "1px solid #313244", overflowY: "auto", fontFamily: "monospace", fontSize: "0.7rem", color: "#a6adc8", whiteSpace: "pre-wrap" }}>
+                {activeJob && (
+                  <div style={{ color: "#f9e2af", marginBottom: "4px" }}>
+                    🧵 {activeJob.request.kind} · {activeJob.status} · {activeJob.progress.done}{activeJob.progress.total > 0 ? `/${activeJob.progress.total}` : ""} {activeJob.progress.message}
+                    <button onClick={handleCancelJob} style={{ marginLeft: "8px", fontSize: "0.65rem", padding: "0 6px", borderRadius: "4px", border: "none", cursor: "pointer", backgroundColor: "#f38ba8", color: "#1e1e2e" }}>취소</button>
+                  </div>
+                )}
                 {log || "Ready..."}
               </div>
             </div>
@@ -261,7 +355,7 @@ export default function Genifier() {
               <div style={{ flex: 1, overflowY: "auto", padding: "20px" }}>
                 {documents.length === 0 ? (
                   <div style={{ color: "#585b70", textAlign: "center", marginTop: "50px" }}>
-                    아직 저장된 문서가 없습니다. <br /> 상단에서 PDF를 선택하고 Step 1을 실행해주세요.
+                    아직 저장된 문서가 없습니다. <br /> 상단에서 문서 폴더를 선택하고 Step 1을 실행해주세요.
                   </div>
                 ) : (
                   documents.map((doc, i) => <DocumentItem key={i} doc={doc} />)
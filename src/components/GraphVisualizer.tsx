@@ -5,7 +5,7 @@ import { invoke } from '@tauri-apps/api/core';
 // 🌟 Rust 데이터 구조와 일치하는 인터페이스 정의
 interface GraphNode {
   id: string;
-  group: string; // "event" | "document" | "entity" | "chunk"
+  group: string; // "event" | "folder" | "document" | "entity" | "chunk"
   label: string;
   info?: string; // Rust의 Option<String>은 undefined일 수 있음
   val: number;   // 🆕 Rust에서 추가된 노드 크기 값
@@ -153,6 +153,7 @@ const GraphVisualizer = ({ refreshKey, viewMode = "all", onNodeClick }: GraphVis
           nodeColor={(node: any) => {
             if (node === hoverNode) return '#f38ba8';
             switch (node.group) {
+              case 'folder': return '#a6e3a1';   // 초록
               case 'document': return '#89b4fa'; // 파랑
               case 'entity': return '#fab387';   // 주황
               case 'chunk': return '#45475a';    // 회색
@@ -165,7 +166,7 @@ const GraphVisualizer = ({ refreshKey, viewMode = "all", onNodeClick }: GraphVis
       {data.nodes.length === 0 && (
         <div style={{ position: "absolute", top: "50%", left: "50%", transform: "translate(-50%, -50%)", color: "#45475a", pointerEvents: "none", textAlign: "center" }}>
           <h3>데이터가 없습니다</h3>
-          <p>우측 패널에서 PDF 폴더를 선택하고 분석을 시작하세요.</p>
+          <p>우측 패널에서 문서 폴더를 선택하고 분석을 시작하세요.</p>
         </div>
       )}
     </div>